@@ -0,0 +1,62 @@
+use common::*;
+use std::collections::BTreeSet;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+   type Input = Vec<i32>;
+   type A = i32;
+   type B = i32;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Vec<i32>, ParseError> {
+      contents.split_whitespace()
+         .map(|x| parse_at(contents, x))
+         .collect()
+   }
+
+   fn part_a(&self, movements: &Vec<i32>) -> i32 {
+      a(movements)
+   }
+
+   fn part_b(&self, movements: &Vec<i32>) -> i32 {
+      b(movements)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![]
+   }
+}
+
+fn a(movements: &Vec<i32>) -> i32 {
+   let mut freq = 0;
+   for movement in movements {
+      freq = freq + movement;
+   }
+
+   freq
+}
+
+fn b(movements: &Vec<i32>) -> i32 {
+   let mut seen = BTreeSet::new();
+
+   let mut freq = 0;
+   let mut itr = movements.iter();
+   loop {
+      match itr.next() {
+         Some(movement) => {
+            freq = freq + movement;
+
+            if seen.contains(&freq) {
+               break;
+            }
+
+            seen.insert(freq);
+         }
+         None => {
+            itr = movements.iter();
+         }
+      }
+   }
+
+   freq
+}
\ No newline at end of file
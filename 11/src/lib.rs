@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use common::*;
+use common::coordinates::Grid;
+use common::coordinates::Loci;
+use common::coordinates::OffsetLociX;
+use common::coordinates::OffsetLociY;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+   type Input = Grid<isize>;
+   type A = String;
+   type B = String;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Grid<isize>, ParseError> {
+      let serial_number = parse_at(contents, contents.trim())?;
+
+      let mut grid = Grid::new_offset(0, 300, 300, 1, 1);
+
+      for loci in grid.locis() {
+         let power_level = calc_power_level(&loci, serial_number);
+
+         grid.set_loci(&loci, power_level);
+      }
+
+      Ok(grid)
+   }
+
+   fn part_a(&self, grid: &Grid<isize>) -> String {
+      a(grid)
+   }
+
+   fn part_b(&self, grid: &Grid<isize>) -> String {
+      b(grid)
+   }
+}
+
+fn a(grid: &Grid<isize>) -> String {
+   let mut max_power = 0;
+   let mut max_loci = Loci::new(0, 0);
+   for loci in grid.locis() {
+      // make sure not to extend outside of what we can check
+      if loci.x() < (grid.x_max() - 3) && loci.y() < (grid.x_max() - 3) {
+         let square_power = sum_power_simple(&loci, 3, grid);
+         if square_power > max_power {
+            max_power = square_power;
+            max_loci = loci.clone();
+         }
+
+         //print!("{:4}", square_power);
+         //
+         //if loci.x() == (grid.x_max() - 4) {
+         //   println!();
+         //}
+      }
+   }
+
+   format!("{},{}", max_loci.x(), max_loci.y())
+}
+
+fn b(grid: &Grid<isize>) -> String {
+   let mut max_power = 0;
+   let mut max_loci = Loci::new(0, 0);
+   let mut max_size= 0;
+
+   let mut memoizer: Grid<HashMap<isize, isize>> = Grid::new_offset(HashMap::new(), 300, 300, 1, 1);
+
+   for length in 1..=20 {
+      for x in 1..=grid.x_max() - length {
+         for y in 1..=grid.y_max() - length {
+            let square_power = sum_power(x, y, length, grid, &mut memoizer);
+            if square_power > max_power {
+               max_power = square_power;
+               max_loci = Loci::new(x, y);
+               max_size = length;
+            }
+         }
+      }
+   }
+
+   format!("{},{},{}", max_loci.x(), max_loci.y(), max_size)
+}
+
+pub fn calc_power_level(loci: &Loci, serial_number: isize) -> isize {
+   let rack_id = loci.x() + 10;
+
+   let mut power_level = rack_id * loci.y();
+   power_level += serial_number;
+   power_level *= rack_id;
+   power_level = (power_level % 1000) / 100;
+   power_level -= 5;
+
+   return power_level;
+}
+
+fn sum_power_simple(loci: &Loci, length: isize, grid: &Grid<isize>) -> isize {
+   // assume the loci has enough space in the grid
+   let mut sum = 0;
+   for x in loci.x()..loci.x() + length {
+      for y in loci.y()..loci.y() + length {
+         //println!("{:?} {:?} {:?}", loci, loci.add(x, y), grid.get_loci(&loci.add(x, y)));
+         sum += grid.get(x, y);
+      }
+   }
+
+   return sum;
+}
+
+fn sum_power(x: isize, y: isize, length: isize, grid: &Grid<isize>, memoizer: &mut Grid<HashMap<isize, isize>>) -> isize {
+   // assume the loci has enough space in the grid
+   let result;
+   if length <= 3 {
+      // drop out to our simple case
+      result = sum_power_simple(&Loci::new(x, y), length, grid);
+   } else {
+      let mut sum = 0;
+      for xi in x..x + length {
+         sum += grid.get(xi, y);
+      }
+      for yi in y + 1..y + length {
+         sum += grid.get(x, yi);
+      }
+
+      // the calling function makes sure this exists
+      sum += memoizer.get(x + 1, y + 1).get(&(length - 1)).unwrap();
+
+      result = sum;
+   }
+
+   memoizer.get_mut(x, y)
+      .insert(length, result);
+
+   result
+}
\ No newline at end of file
@@ -0,0 +1,151 @@
+use regex::Regex;
+
+use common::*;
+use common::coordinates::InfiniteGrid;
+use common::coordinates::OffsetLociX;
+
+pub struct Transition {
+   state: Vec<bool>,
+   plant_grows: bool,
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+   type Input = (Vec<bool>, Vec<Transition>);
+   type A = isize;
+   type B = isize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(Vec<bool>, Vec<Transition>), ParseError> {
+      let initial_state_re: Regex = Regex::new(r"initial state: ([#.]+)").unwrap();
+      let transition_re: Regex = Regex::new(r"([#.]{5}) => ([#.])").unwrap();
+
+      let mut lines = contents.lines();
+
+
+      let initial_state: Vec<bool> = lines.next()
+         .and_then(|line| initial_state_re.captures(line))
+         .ok_or_else(|| ParseError::new(1, 1, "expected our initial state like initial state: #..#.#"))?[1]
+         .chars()
+         .map(|c| c == '#')
+         .collect();
+
+      let transitions: Vec<Transition> = lines.skip(1)
+         .map(|row| {
+            let parsed_row = transition_re.captures(row)
+               .ok_or_else(|| ParseError::at(contents, row, "expected a transition like ...## => #"))?;
+
+            let state = parsed_row[1].chars()
+               .map(|c| c == '#')
+               .collect();
+
+            let plant_grows = parsed_row[2].chars().next().unwrap() == '#';
+
+            Ok(Transition {
+               state,
+               plant_grows,
+            })
+         })
+         // filter out no-grow states
+         .filter(|transition| transition.as_ref().map(|t| t.plant_grows).unwrap_or(true))
+         .collect::<Result<_, _>>()?;
+
+      Ok((initial_state, transitions))
+   }
+
+   fn part_a(&self, (initial_state, transitions): &(Vec<bool>, Vec<Transition>)) -> isize {
+      a(initial_state, transitions)
+   }
+
+   fn part_b(&self, (initial_state, transitions): &(Vec<bool>, Vec<Transition>)) -> isize {
+      b(initial_state, transitions)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![Example::sample().only(Part::A).a(325)]
+   }
+}
+
+fn a(initial_state: &Vec<bool>, transitions: &Vec<Transition>) -> isize {
+   run_generations(20, initial_state, transitions).0
+}
+
+fn b(initial_state: &Vec<bool>, transitions: &Vec<Transition>) -> isize {
+   // assume we're going to have a continuously moving plant colony to the right
+   let (result, first_plant, num_plants) = run_generations(1000, initial_state, transitions);
+   let movement = run_generations(1001, initial_state, transitions).1 - first_plant;
+
+   // sanity check
+   {
+      let move_distance = (1010 - 1000) * movement;
+      let offset_result = result + (move_distance * num_plants);
+      let (check, _, _) = run_generations(1010, initial_state, transitions);
+
+      assert_eq!(offset_result, check);
+   }
+
+   result + (((50000000000 - 1000) * movement) * num_plants)
+}
+
+fn run_generations(generations: u64, initial_state: &Vec<bool>, transitions: &Vec<Transition>) -> (isize, isize, isize) {
+   // a single row of pots that grows whichever way our plants spread
+   let mut plants = InfiniteGrid::new(false);
+   for (x, plant) in initial_state.iter().enumerate() {
+      if *plant {
+         plants.set(x as isize, 0, true);
+      }
+   }
+
+   for _generation in 1..=generations {
+      let mut next_plants = InfiniteGrid::new(false);
+
+      // a plant can only grow within 2 pots of another
+      for x in plants.x_min() - 2..plants.x_max() + 2 {
+         let pots: Vec<bool> = (x - 2..=x + 2)
+            .map(|pot| *plants.get(pot, 0))
+            .collect();
+
+         if transition(&pots, transitions) {
+            next_plants.set(x, 0, true);
+         }
+      }
+
+      plants = next_plants;
+   }
+
+   let mut sum = 0;
+   let mut lowest_plant = isize::MAX;
+   let mut num_plants = 0;
+   for x in plants.x_range().filter(|x| *plants.get(*x, 0)) {
+      if x < lowest_plant {
+         lowest_plant = x;
+      }
+
+      sum += x;
+      num_plants += 1;
+   }
+
+   (sum, lowest_plant, num_plants)
+}
+
+fn transition(plant_state: &[bool], transitions: &Vec<Transition>) -> bool {
+   for transition in transitions.iter() {
+      if &transition.state[..] == plant_state {
+         return transition.plant_grows;
+      }
+   }
+
+   return false;
+}
+
+//fn plants_to_string(plants: &Vec<bool>) -> String {
+//   plants.iter()
+//      .map(|plant| {
+//         if *plant {
+//            '#'
+//         } else {
+//            '.'
+//         }
+//      })
+//      .collect()
+//}
\ No newline at end of file
@@ -0,0 +1,362 @@
+use common::*;
+
+use std::fmt;
+use common::animation::Recorder;
+use common::coordinates::Grid;
+use common::coordinates::Loci;
+use common::coordinates::OffsetLociX;
+use common::coordinates::OffsetLociY;
+
+#[derive(Debug)]
+#[derive(Copy, Clone)]
+enum Turn {
+   Left,
+   Straight,
+   Right,
+}
+
+#[derive(Debug)]
+#[derive(Copy, Clone)]
+pub struct Train {
+   dir: char,
+   last_turn: Turn,
+   moved: bool,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+   type Input = (Grid<Option<Train>>, Grid<char>);
+   type A = String;
+   type B = String;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(Grid<Option<Train>>, Grid<char>), ParseError> {
+      let input: Grid<char> = Grid::parse_ascii(contents, ' ')?;
+
+      let mut tracks = Grid::new(' ', input.width(), input.height());
+      let mut trains = Grid::new(None, input.width(), input.height());
+
+      for (loci, c) in input.enumerate() {
+         let (x, y) = (loci.x() as usize, loci.y() as usize);
+
+         let mut track = None;
+         let mut train = None;
+         match *c {
+            ' ' => {} // do nothing for a space,
+            trk @ '-' | trk @ '|' | trk @ '\\' | trk @ '/' | trk @ '+' => {
+               track = Some(trk)
+            }
+            trn @ '>' | trn @ '<' | trn @ '^' | trn @ 'v' => {
+               track = Some(under_track(&loci, &input)
+                  .ok_or_else(|| ParseError::new(y + 1, x + 1, "can't tell what track this train is on"))?);
+               train = Some(trn);
+            }
+            u => return Err(ParseError::new(y + 1, x + 1, format!("unexpected {:?}, expected a track or a train", u)))
+         }
+
+         if let Some(t) = track {
+            tracks.set_loci(&loci, t);
+         }
+         if let Some(t) = train {
+            trains.set_loci(&loci, Some(Train {
+               dir: t,
+               last_turn: Turn::Right,
+               moved: false,
+            }));
+         }
+      }
+
+      Ok((trains, tracks))
+   }
+
+   fn part_a(&self, (trains, tracks): &(Grid<Option<Train>>, Grid<char>)) -> String {
+      a(trains, tracks)
+   }
+
+   fn part_b(&self, (trains, tracks): &(Grid<Option<Train>>, Grid<char>)) -> String {
+      b(trains, tracks, &mut |_| {})
+   }
+
+   // part b needs more trains than our first sample has
+   fn examples(&self) -> Vec<Example> {
+      vec![
+         Example::sample().only(Part::A),
+         Example::file("sample_input_2.txt").only(Part::B),
+      ]
+   }
+
+   // part b, since it keeps going until there's only one train left
+   fn animate(&self, (trains, tracks): &(Grid<Option<Train>>, Grid<char>), recorder: &mut Recorder) -> bool {
+      let mut map = tracks.clone();
+      b(trains, tracks, &mut |trains| {
+         map = train_map(trains, tracks);
+         recorder.tick(4, &map, &map_color);
+      });
+      recorder.finish(4, &map, &map_color);
+
+      true
+   }
+}
+
+fn a(initial_trains: &Grid<Option<Train>>, tracks: &Grid<char>) -> String {
+   let mut moved_state = true;
+   let mut trains = initial_trains.clone();
+
+   let mut collision = Loci::new(0, 0);
+   'outer: loop {
+      // print
+      //for y in trains.y_range() {
+      //   for x in trains.x_range() {
+      //      match trains.get(x, y) {
+      //         Some(t) => print!("{}", t),
+      //         None => print!("{}", tracks.get(x, y))
+      //      }
+      //   }
+      //   println!();
+      //}
+
+      for y in trains.y_range() {
+         for x in trains.x_range() {
+            let successful = trains.get(x, y)
+               .filter(|train| train.moved != moved_state)
+               .map(|train| {
+                  // remove the last train
+                  trains.set(x, y, None);
+
+                  let (next_x, next_y, mut next_train) = next_train(&train, x, y, tracks);
+
+                  // check for collision
+                  if trains.get(next_x, next_y).is_some() {
+                     collision = Loci::new(next_x, next_y);
+
+                     false
+                  } else {
+                     next_train.moved = !next_train.moved;
+
+                     trains.set(next_x, next_y, Some(next_train));
+
+                     true
+                  }
+               })
+               .unwrap_or(true);
+
+            if !successful {
+               break 'outer;
+            }
+         }
+      }
+
+      moved_state = !moved_state;
+   }
+
+   format!("{},{}", collision.x(), collision.y())
+}
+
+// `on_tick` sees our trains before each tick
+fn b<F>(initial_trains: &Grid<Option<Train>>, tracks: &Grid<char>, on_tick: &mut F) -> String where
+   F: FnMut(&Grid<Option<Train>>) {
+   let mut moved_state = true;
+   let mut trains = initial_trains.clone();
+
+   let mut last_train = Loci::new(0, 0);
+   loop {
+      on_tick(&trains);
+
+      let mut train_count = 0;
+      for y in trains.y_range() {
+         for x in trains.x_range() {
+            trains.get(x, y)
+               .filter(|train| train.moved != moved_state)
+               .map(|train| {
+                  // remove the last train
+                  trains.set(x, y, None);
+
+                  let (next_x, next_y, mut next_train) = next_train(&train, x, y, tracks);
+
+                  // check for collision
+                  match trains.get(next_x, next_y) {
+                     Some(collided_train) => {
+                        //println!("Crash at: {} {}", next_x, next_y);
+
+                        // subtract for our collided train if we already counted it
+                        if collided_train.moved == moved_state {
+                           train_count -= 1;
+                        }
+
+                        // clear the train
+                        trains.set(next_x, next_y, None);
+                     }
+                     None => {
+                        train_count += 1;
+                        next_train.moved = !next_train.moved;
+
+                        // set our train
+                        trains.set(next_x, next_y, Some(next_train));
+                        // save this train's location
+                        last_train = Loci::new(next_x, next_y);
+                     }
+                  }
+               });
+         }
+      }
+
+      moved_state = !moved_state;
+
+      if train_count == 1 {
+         break;
+      }
+   }
+   // and once more so we see the last train standing
+   on_tick(&trains);
+
+   format!("{},{}", last_train.x(), last_train.y())
+}
+
+// our trains drawn on top of their tracks
+fn train_map(trains: &Grid<Option<Train>>, tracks: &Grid<char>) -> Grid<char> {
+   let mut map = tracks.clone();
+   for (loci, train) in trains.enumerate() {
+      if let Some(train) = train {
+         map.set_loci(&loci, train.dir);
+      }
+   }
+
+   map
+}
+
+fn map_color(c: &char) -> Option<u32> {
+   match c {
+      ' ' => Some(0x000000FF),
+      '^' | '>' | 'v' | '<' => Some(0xE74C3CFF),
+      _ => Some(0x808080FF),
+   }
+}
+
+fn next_train(train: &Train, x: isize, y: isize, tracks: &Grid<char>) -> (isize, isize, Train) {
+   let mut next_x = x;
+   let mut next_y = y;
+   let next_train = match train.dir {
+      '^' => {
+         next_y = y - 1;
+
+         match tracks.get(x, next_y) {
+            '\\' => train.turn('<'),
+            '/' => train.turn('>'),
+            '+' => train.intersection(),
+            _ => train.clone(),
+         }
+      }
+      '>' => {
+         next_x = x + 1;
+
+         match tracks.get(next_x, y) {
+            '\\' => train.turn('v'),
+            '/' => train.turn('^'),
+            '+' => train.intersection(),
+            _ => train.clone(),
+         }
+      }
+      'v' => {
+         next_y = y + 1;
+
+         match tracks.get(x, next_y) {
+            '\\' => train.turn('>'),
+            '/' => train.turn('<'),
+            '+' => train.intersection(),
+            _ => train.clone(),
+         }
+      }
+      '<' => {
+         next_x = x - 1;
+
+         match tracks.get(next_x, y) {
+            '\\' => train.turn('^'),
+            '/' => train.turn('v'),
+            '+' => train.intersection(),
+            _ => train.clone(),
+         }
+      }
+      u => panic!("Unexpected train: {}", u)
+   };
+
+   (next_x, next_y, next_train)
+}
+
+// Get the track under a train
+// nothing when the tracks around us don't make sense
+fn under_track(loci: &Loci, input: &Grid<char>) -> Option<char> {
+   fn check_connection(expected: char, input: Option<&char>) -> bool {
+      input.map(|&input| input == expected || input == '\\' || input == '/' || input == '+')
+         .unwrap_or(false)
+   }
+
+   let up = check_connection('|', input.try_get(loci.x(), loci.y() - 1));
+   let down = check_connection('|', input.try_get(loci.x(), loci.y() + 1));
+   let left = check_connection('-', input.try_get(loci.x() - 1, loci.y()));
+   let right = check_connection('-', input.try_get(loci.x() + 1, loci.y()));
+
+   if up && down && left && right {
+      Some('+')
+   } else if (down && right && !up && !left) || (up && left && !down && !right) {
+      Some('/')
+   } else if (down && left && !up && !right) || (up && right && !down && !left) {
+      Some('\\')
+   } else if up && down {
+      Some('|')
+   } else if right && left {
+      Some('-')
+   } else {
+      None
+   }
+}
+
+impl Train {
+   fn intersection(&self) -> Train {
+      let next_turn = self.next_turn();
+      let next_dir = match next_turn {
+         Turn::Left => match self.dir {
+            '^' => '<',
+            '<' => 'v',
+            'v' => '>',
+            '>' => '^',
+            u => panic!("Unexpected train: {}", u)
+         },
+         Turn::Right => match self.dir {
+            '^' => '>',
+            '>' => 'v',
+            'v' => '<',
+            '<' => '^',
+            u => panic!("Unexpected train: {}", u)
+         },
+         Turn::Straight => self.dir,
+      };
+
+      Train {
+         dir: next_dir,
+         last_turn: next_turn,
+         moved: self.moved,
+      }
+   }
+
+   fn turn(&self, dir: char) -> Train {
+      Train {
+         dir,
+         last_turn: self.last_turn,
+         moved: self.moved,
+      }
+   }
+
+   fn next_turn(&self) -> Turn {
+      match self.last_turn {
+         Turn::Left => Turn::Straight,
+         Turn::Straight => Turn::Right,
+         Turn::Right => Turn::Left,
+      }
+   }
+}
+
+impl fmt::Display for Train {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{}", self.dir)
+   }
+}
\ No newline at end of file
@@ -0,0 +1,461 @@
+use std::fmt;
+use std::collections::HashSet;
+
+use common::coordinates::Grid;
+use common::coordinates::Loci;
+use common::coordinates::OffsetLociX;
+use common::coordinates::OffsetLociY;
+use common::search::Search;
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Unit {
+   species: Species,
+   loci: Loci,
+   health: isize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Species {
+   Elf,
+   Goblin,
+}
+
+/// How a battle is fought, the default is the puzzle's
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct BattleRules {
+   pub elf: SpeciesRules,
+   pub goblin: SpeciesRules,
+   pub lose: Lose,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SpeciesRules {
+   pub attack_power: isize,
+   /// what each unit starts with
+   pub health: isize,
+   /// how many squares a unit can move a turn before it attacks, 0 holds its ground
+   pub moves: usize,
+}
+
+/// When a side has lost, the battle ends right away with the other side winning
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Lose {
+   /// the puzzle's, when there's nobody left to fight
+   #[default]
+   Eliminated,
+   /// the first time one of them dies
+   AnyDeath(Species),
+   /// once more than this many of them have died
+   Casualties(Species, usize),
+}
+
+impl BattleRules {
+   pub fn species(&self, species: Species) -> &SpeciesRules {
+      match species {
+         Species::Elf => &self.elf,
+         Species::Goblin => &self.goblin,
+      }
+   }
+
+   /// The puzzle's rules with our elves hitting harder
+   pub fn elf_attack_power(attack_power: isize) -> BattleRules {
+      let mut rules = BattleRules::default();
+      rules.elf.attack_power = attack_power;
+
+      rules
+   }
+
+   // whether losing this many of a species loses the battle
+   fn lost(&self, species: Species, deaths: usize) -> bool {
+      match self.lose {
+         Lose::Eliminated => false,
+         Lose::AnyDeath(losers) => losers == species && deaths > 0,
+         Lose::Casualties(losers, budget) => losers == species && deaths > budget,
+      }
+   }
+}
+
+impl Default for SpeciesRules {
+   fn default() -> SpeciesRules {
+      SpeciesRules {
+         attack_power: 3,
+         health: 200,
+         moves: 1,
+      }
+   }
+}
+
+/// Everything that happens in a battle, units are their index in `Battle::units`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Event {
+   Moved { unit: usize, from: Loci, to: Loci },
+   /// `health` is what our target has left, it dies at 0 or below
+   Attacked { unit: usize, target: usize, damage: isize, health: isize },
+   Died { unit: usize, killer: usize },
+   /// `rounds` is how many full rounds we've had
+   RoundEnded { rounds: usize },
+   /// The health left on both sides times the full rounds we had is our outcome,
+   /// the losers only have any left when our rules ended the battle early
+   CombatEnded { rounds: usize, winner: Species, outcome: isize },
+}
+
+/// A fight between our elves and goblins, a unit's turn or a whole round at a time
+#[derive(Clone, Debug)]
+pub struct Battle {
+   map: Grid<bool>,
+   units: Vec<Unit>,
+   rules: BattleRules,
+   rounds: usize,
+   // who still has a turn this round, in reading order from when it started
+   turns: Vec<usize>,
+   turn: usize,
+   over: bool,
+   winner: Option<Species>,
+}
+
+impl Battle {
+   /// `map` is true for walls, and every unit starts with the health our rules give its species
+   pub fn new(units: &[Unit], map: &Grid<bool>, rules: BattleRules) -> Battle {
+      let units = units.iter()
+         .map(|unit| Unit {
+            health: rules.species(unit.species).health,
+            ..*unit
+         })
+         .collect();
+
+      let mut battle = Battle {
+         map: map.clone(),
+         units,
+         rules,
+         rounds: 0,
+         turns: vec![],
+         turn: 0,
+         over: false,
+         winner: None,
+      };
+      battle.start_round(&mut vec![]);
+
+      battle
+   }
+
+   pub fn map(&self) -> &Grid<bool> {
+      &self.map
+   }
+
+   /// Every unit we started with, the dead ones included so their indexes never change
+   pub fn units(&self) -> &[Unit] {
+      &self.units
+   }
+
+   /// How many full rounds we've had
+   pub fn rounds(&self) -> usize {
+      self.rounds
+   }
+
+   pub fn rules(&self) -> &BattleRules {
+      &self.rules
+   }
+
+   /// A battle without both species is over before it starts
+   pub fn is_over(&self) -> bool {
+      self.over
+   }
+
+   /// Who won once the battle is over
+   pub fn winner(&self) -> Option<Species> {
+      self.winner
+   }
+
+   /// Our outcome once the battle is over
+   pub fn outcome(&self) -> Option<isize> {
+      if self.over {
+         Some(self.rounds as isize * self.remaining_health())
+      } else {
+         None
+      }
+   }
+
+   fn remaining_health(&self) -> isize {
+      self.units.iter()
+         .filter(|unit| unit.is_alive())
+         .map(|unit| unit.health)
+         .sum()
+   }
+
+   fn start_round(&mut self, events: &mut Vec<Event>) {
+      let mut turns: Vec<usize> = (0..self.units.len()).collect();
+      turns.sort_by_key(|&i| self.units[i].loci);
+
+      self.turns = turns;
+      self.turn = 0;
+
+      // nobody will ever find a target without both species, so we'd keep counting empty rounds
+      let elves = self.alive().any(|i| self.units[i].species == Species::Elf);
+      let goblins = self.alive().any(|i| self.units[i].species == Species::Goblin);
+      match (elves, goblins) {
+         (true, false) => self.end(Species::Elf, events),
+         (false, true) => self.end(Species::Goblin, events),
+         (false, false) => self.over = true,
+         (true, true) => {}
+      }
+   }
+
+   /// The next living unit takes its turn, if it's the last one this round the round ends as well.
+   /// Nothing happens once the battle is over
+   pub fn step_turn(&mut self) -> Vec<Event> {
+      let mut events = vec![];
+      if self.over {
+         return events;
+      }
+
+      while self.turn < self.turns.len() && !self.units[self.turns[self.turn]].is_alive() {
+         self.turn += 1;
+      }
+
+      if let Some(&i) = self.turns.get(self.turn) {
+         self.turn += 1;
+         self.take_turn(i, &mut events);
+      }
+
+      if !self.over && self.turns[self.turn..].iter().all(|&i| !self.units[i].is_alive()) {
+         self.rounds += 1;
+         events.push(Event::RoundEnded { rounds: self.rounds });
+
+         self.start_round(&mut events);
+      }
+
+      events
+   }
+
+   /// Turns until our round or the battle ends
+   pub fn step_round(&mut self) -> Vec<Event> {
+      let mut events = vec![];
+
+      while !self.over {
+         let turn_events = self.step_turn();
+         let round_ended = turn_events.iter().any(|event| matches!(event, Event::RoundEnded { .. }));
+         events.extend(turn_events);
+
+         if round_ended {
+            break;
+         }
+      }
+
+      events
+   }
+
+   /// Every round left, until the battle is over
+   pub fn run(&mut self) -> Vec<Event> {
+      let mut events = vec![];
+
+      while !self.over {
+         events.extend(self.step_round());
+      }
+
+      events
+   }
+
+   fn take_turn(&mut self, i: usize, events: &mut Vec<Event>) {
+      let species = self.units[i].species;
+      let enemies: Vec<usize> = self.alive()
+         .filter(|&j| self.units[j].species == species.enemy())
+         .collect();
+
+      // no enemies to fight so we're done
+      if enemies.is_empty() {
+         self.end(species, events);
+
+         return;
+      }
+
+      // move until we're next to an enemy
+      for _ in 0..self.rules.species(species).moves {
+         if self.in_range(i, &enemies).next().is_some() {
+            break;
+         }
+
+         match self.closest_enemy_step(i, &enemies) {
+            Some(to) => {
+               let from = self.units[i].loci;
+               self.units[i].loci = to;
+
+               events.push(Event::Moved { unit: i, from, to });
+            }
+            None => break,
+         }
+      }
+
+      // the weakest enemy next to us, with reading order breaking any ties
+      let target = self.in_range(i, &enemies)
+         .min_by_key(|&j| (self.units[j].health, self.units[j].loci));
+
+      if let Some(target) = target {
+         let damage = self.attack_power(species);
+         self.units[target].health -= damage;
+
+         let health = self.units[target].health;
+         events.push(Event::Attacked { unit: i, target, damage, health });
+         if health <= 0 {
+            events.push(Event::Died { unit: target, killer: i });
+
+            if self.rules.lost(species.enemy(), self.deaths(species.enemy())) {
+               self.end(species, events);
+            }
+         }
+      }
+   }
+
+   fn end(&mut self, winner: Species, events: &mut Vec<Event>) {
+      self.over = true;
+      self.winner = Some(winner);
+
+      events.push(Event::CombatEnded {
+         rounds: self.rounds,
+         winner,
+         outcome: self.rounds as isize * self.remaining_health(),
+      });
+   }
+
+   fn attack_power(&self, species: Species) -> isize {
+      self.rules.species(species).attack_power
+   }
+
+   /// How many of a species have died so far
+   pub fn deaths(&self, species: Species) -> usize {
+      self.units.iter()
+         .filter(|unit| unit.species == species && !unit.is_alive())
+         .count()
+   }
+
+   fn alive(&self) -> impl Iterator<Item=usize> + '_ {
+      (0..self.units.len()).filter(move |&i| self.units[i].is_alive())
+   }
+
+   fn in_range<'a>(&'a self, i: usize, enemies: &'a [usize]) -> impl Iterator<Item=usize> + 'a {
+      let loci = self.units[i].loci;
+
+      enemies.iter()
+         .copied()
+         .filter(move |&j| self.units[j].is_alive() && self.units[j].loci.distance(&loci) == 1)
+   }
+
+   // the first step towards the closest square next to an enemy, reading order breaks any ties
+   fn closest_enemy_step(&self, i: usize, enemies: &[usize]) -> Option<Loci> {
+      let start = self.units[i].loci;
+      let occupied: HashSet<Loci> = self.alive()
+         .filter(|&j| j != i)
+         .map(|j| self.units[j].loci)
+         .collect();
+      let targets: HashSet<Loci> = enemies.iter()
+         .flat_map(|&j| self.units[j].loci.orthogonal())
+         .collect();
+
+      let open_neighbors = |loci: &Loci| {
+         loci.orthogonal()
+            .filter(|neighbor| !*self.map.get_loci(neighbor) && !occupied.contains(neighbor))
+            .collect::<Vec<Loci>>()
+      };
+
+      let target = *Search::new(start, open_neighbors)
+         .tie_break(|loci| *loci)
+         .until(|loci| *loci != start && targets.contains(loci))
+         .bfs()
+         .goal()?;
+
+      // walk back from our target, the first square next to us we reach is where we step
+      Search::new(target, open_neighbors)
+         .tie_break(|loci| *loci)
+         .until(|loci| loci.distance(&start) == 1)
+         .bfs()
+         .goal()
+         .cloned()
+   }
+}
+
+/// Our map like the puzzle draws it, with the health of each row's units after it
+impl fmt::Display for Battle {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      for y in self.map.y_range() {
+         let mut row_units = vec![];
+
+         for x in self.map.x_range() {
+            let loci = Loci::new(x, y);
+            match self.units.iter().find(|unit| unit.is_alive() && unit.loci == loci) {
+               Some(unit) => {
+                  write!(f, "{}", unit.species)?;
+                  row_units.push(format!("{}({})", unit.species, unit.health));
+               }
+               None if *self.map.get_loci(&loci) => write!(f, "#")?,
+               None => write!(f, ".")?,
+            }
+         }
+
+         if !row_units.is_empty() {
+            write!(f, "   {}", row_units.join(", "))?;
+         }
+         writeln!(f)?;
+      }
+
+      Ok(())
+   }
+}
+
+impl Unit {
+   /// With the puzzle's starting health, a battle gives it whatever its rules say
+   pub fn new(species: Species, loci: Loci) -> Unit {
+      Unit {
+         species,
+         loci,
+         health: SpeciesRules::default().health,
+      }
+   }
+
+   pub fn species(&self) -> Species {
+      self.species
+   }
+
+   pub fn loci(&self) -> Loci {
+      self.loci
+   }
+
+   pub fn health(&self) -> isize {
+      self.health
+   }
+
+   pub fn is_alive(&self) -> bool {
+      self.health > 0
+   }
+}
+
+impl Species {
+   pub fn enemy(&self) -> Species {
+      match self {
+         Species::Elf => Species::Goblin,
+         Species::Goblin => Species::Elf,
+      }
+   }
+}
+
+impl fmt::Display for Species {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         Species::Elf => write!(f, "E"),
+         Species::Goblin => write!(f, "G"),
+      }
+   }
+}
+
+impl fmt::Display for Event {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         Event::Moved { unit, from, to } =>
+            write!(f, "unit {} moved from {},{} to {},{}", unit, from.x(), from.y(), to.x(), to.y()),
+         Event::Attacked { unit, target, damage, health } =>
+            write!(f, "unit {} hit unit {} for {}, leaving it {}", unit, target, damage, health),
+         Event::Died { unit, killer } => write!(f, "unit {} was killed by unit {}", unit, killer),
+         Event::RoundEnded { rounds } => write!(f, "round {} ended", rounds),
+         Event::CombatEnded { rounds, winner, outcome } =>
+            write!(f, "{} won after {} full rounds, outcome {}", winner, rounds, outcome),
+      }
+   }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use std::env;
+use std::process;
+
+use common::*;
+use day15::Day15;
+use day15::battle::Battle;
+use day15::battle::BattleRules;
+use day15::replay::{Replay, Viewer};
+
+// cargo run --bin battleview -- [<input>] [--attack <elf attack power>]
+fn main() {
+   let mut path = "input.txt".to_string();
+   let mut elf_attack_power = 3;
+
+   let mut args = env::args().skip(1);
+   while let Some(arg) = args.next() {
+      match arg.as_ref() {
+         "--attack" | "-a" => {
+            elf_attack_power = args.next()
+               .and_then(|power| power.parse().ok())
+               .unwrap_or_else(|| usage());
+         }
+         _ if arg.starts_with('-') => usage(),
+         _ => path = arg,
+      }
+   }
+
+   let contents = read_input("15", &path)
+      .unwrap_or_else(|e| {
+         eprintln!("{}", e);
+         process::exit(1);
+      });
+   let (units, map) = Day15.parse(&contents, false)
+      .unwrap_or_else(|e| {
+         eprintln!("{}", e.in_file(&path));
+         process::exit(1);
+      });
+
+   let replay = Replay::record(Battle::new(&units, &map, BattleRules::elf_attack_power(elf_attack_power)));
+   Viewer::new(replay).run().unwrap();
+}
+
+fn usage() -> ! {
+   eprintln!("usage: battleview [<input>] [--attack <elf attack power>]");
+   process::exit(1);
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+use common::*;
+use common::animation::Recorder;
+use common::coordinates::Grid;
+use common::coordinates::GridChar;
+use common::coordinates::OffsetLociX;
+use common::coordinates::OffsetLociY;
+use common::threshold::min_winning;
+
+use crate::battle::Battle;
+use crate::battle::BattleRules;
+use crate::battle::Lose;
+use crate::battle::Species;
+use crate::battle::Unit;
+
+pub mod battle;
+pub mod replay;
+
+// what each square of our map starts out as
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Square {
+   Wall,
+   Open,
+   Unit(Species),
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+   type Input = (Vec<Unit>, Grid<bool>);
+   type A = isize;
+   type B = isize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(Vec<Unit>, Grid<bool>), ParseError> {
+      let squares: Grid<Square> = Grid::parse_ascii(contents, Square::Open)?;
+
+      let mut map = Grid::new(false, squares.width(), squares.height());
+      let mut units = vec![];
+      for (loci, square) in squares.enumerate() {
+         match *square {
+            Square::Wall => map.set_loci(&loci, true),
+            Square::Unit(species) => units.push(Unit::new(species, loci)),
+            Square::Open => {}
+         }
+      }
+
+      Ok((units, map))
+   }
+
+   fn part_a(&self, (units, map): &(Vec<Unit>, Grid<bool>)) -> isize {
+      a(units, map)
+   }
+
+   fn part_b(&self, (units, map): &(Vec<Unit>, Grid<bool>)) -> isize {
+      b(units, map)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      let mut examples = Example::numbered("test_input_{}.txt", Part::A,
+                                           vec![36334, 39514, 27755, 28944, 18740, 13400, 13987, 10234]);
+      examples.push(Example::sample().a(27730).b(4988));
+
+      examples
+   }
+
+   // part a's battle, a frame a round
+   fn animate(&self, (units, map): &(Vec<Unit>, Grid<bool>), recorder: &mut Recorder) -> bool {
+      let mut battle = Battle::new(units, map, BattleRules::default());
+
+      recorder.tick(8, &cave_map(&battle), &square_color);
+      while !battle.is_over() {
+         battle.step_round();
+         recorder.tick(8, &cave_map(&battle), &square_color);
+      }
+      recorder.finish(8, &cave_map(&battle), &square_color);
+
+      true
+   }
+}
+
+fn a(input_units: &Vec<Unit>, map: &Grid<bool>) -> isize {
+   let mut battle = Battle::new(input_units, map, BattleRules::default());
+   battle.run();
+
+   battle.outcome().expect("we ran until the battle was over")
+}
+
+fn b(input_units: &Vec<Unit>, map: &Grid<bool>) -> isize {
+   let threshold = min_winning(4..200, |attack_power| {
+      let rules = BattleRules {
+         lose: Lose::AnyDeath(Species::Elf),
+         ..BattleRules::elf_attack_power(attack_power)
+      };
+
+      let mut battle = Battle::new(input_units, map, rules);
+      battle.run();
+
+      if battle.winner() == Some(Species::Elf) {
+         battle.outcome()
+      } else {
+         None
+      }
+   });
+
+   threshold.expect("no answer").outcome
+}
+
+// our map with the units still alive on it
+fn cave_map(battle: &Battle) -> Grid<Square> {
+   let map = battle.map();
+
+   let mut cave = Grid::new(Square::Open, map.width(), map.height());
+   for (loci, wall) in map.enumerate() {
+      if *wall {
+         cave.set_loci(&loci, Square::Wall);
+      }
+   }
+
+   for unit in battle.units().iter().filter(|unit| unit.is_alive()) {
+      cave.set_loci(&unit.loci(), Square::Unit(unit.species()));
+   }
+
+   cave
+}
+
+fn square_color(square: &Square) -> Option<u32> {
+   Some(match square {
+      Square::Wall => 0x303030FF,
+      Square::Open => 0x997F6CFF,
+      Square::Unit(Species::Elf) => 0x298210FF,
+      Square::Unit(Species::Goblin) => 0xC0392BFF,
+   })
+}
+
+impl GridChar for Square {
+   fn from_char(c: char) -> Option<Square> {
+      match c {
+         '#' => Some(Square::Wall),
+         '.' => Some(Square::Open),
+         'E' => Some(Square::Unit(Species::Elf)),
+         'G' => Some(Square::Unit(Species::Goblin)),
+         _ => None,
+      }
+   }
+
+   fn to_char(&self) -> char {
+      match *self {
+         Square::Wall => '#',
+         Square::Open => '.',
+         Square::Unit(Species::Elf) => 'E',
+         Square::Unit(Species::Goblin) => 'G',
+      }
+   }
+}
\ No newline at end of file
@@ -0,0 +1,241 @@
+use std::io;
+use std::io::prelude::*;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use termion::async_stdin;
+use termion::clear;
+use termion::color;
+use termion::cursor;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::style;
+
+use common::coordinates::Loci;
+use common::coordinates::OffsetLociX;
+use common::coordinates::OffsetLociY;
+
+use crate::battle::Battle;
+use crate::battle::Event;
+use crate::battle::Species;
+
+const HEALTH_BAR_WIDTH: usize = 10;
+const PLAY_SPEED: Duration = Duration::from_millis(250);
+
+/// Our battle at the start of every round, so we can go back and forth through it
+#[derive(Clone, Debug)]
+pub struct Replay {
+   frames: Vec<Battle>,
+   // what happened to get to each frame, nothing for our first
+   events: Vec<Vec<Event>>,
+}
+
+impl Replay {
+   /// Runs our battle to the end, a round at a time
+   pub fn record(mut battle: Battle) -> Replay {
+      let mut frames = vec![battle.clone()];
+      let mut events = vec![vec![]];
+
+      while !battle.is_over() {
+         events.push(battle.step_round());
+         frames.push(battle.clone());
+      }
+
+      Replay {
+         frames,
+         events,
+      }
+   }
+
+   pub fn len(&self) -> usize {
+      self.frames.len()
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.frames.is_empty()
+   }
+
+   pub fn frame(&self, frame: usize) -> &Battle {
+      &self.frames[frame]
+   }
+
+   /// What happened between the last frame and this one
+   pub fn events(&self, frame: usize) -> &[Event] {
+      &self.events[frame]
+   }
+
+   /// The first frame after an elf died
+   pub fn first_elf_death(&self) -> Option<usize> {
+      (0..self.len()).find(|&frame| !self.elf_deaths(frame).is_empty())
+   }
+
+   fn elf_deaths(&self, frame: usize) -> Vec<(usize, usize)> {
+      let units = self.frames[frame].units();
+
+      self.events[frame].iter()
+         .filter_map(|event| match *event {
+            Event::Died { unit, killer } if units[unit].species() == Species::Elf => Some((unit, killer)),
+            _ => None,
+         })
+         .collect()
+   }
+}
+
+/// Plays a `Replay` back in our terminal
+pub struct Viewer {
+   replay: Replay,
+   frame: usize,
+   playing: bool,
+   // the most health any unit started with, so every health bar is to the same scale, at least 1 so we can divide by it
+   max_health: isize,
+}
+
+impl Viewer {
+   pub fn new(replay: Replay) -> Viewer {
+      let max_health = replay.frame(0).units().iter()
+         .map(|unit| unit.health())
+         .max()
+         .unwrap_or(1)
+         .max(1);
+
+      Viewer {
+         replay,
+         frame: 0,
+         playing: false,
+         max_health,
+      }
+   }
+
+   /// Takes over our terminal until we're told to quit
+   pub fn run(&mut self) -> io::Result<()> {
+      let mut output = io::stdout().into_raw_mode()?;
+      let mut keys = async_stdin().keys();
+
+      write!(output, "{}", cursor::Hide)?;
+
+      let mut last_step = Instant::now();
+      let mut dirty = true;
+      loop {
+         if dirty {
+            self.draw(&mut output)?;
+            dirty = false;
+         }
+
+         match keys.next() {
+            Some(Ok(key)) => {
+               if !self.handle(key) {
+                  break;
+               }
+               dirty = true;
+            }
+            Some(Err(error)) => return Err(error),
+            None => thread::sleep(Duration::from_millis(20)),
+         }
+
+         if self.playing && last_step.elapsed() >= PLAY_SPEED {
+            self.step(1);
+            self.playing = self.frame + 1 < self.replay.len();
+            last_step = Instant::now();
+            dirty = true;
+         }
+      }
+
+      write!(output, "{}{}{}", clear::All, cursor::Goto(1, 1), cursor::Show)?;
+      output.flush()
+   }
+
+   // false when we should quit
+   fn handle(&mut self, key: Key) -> bool {
+      match key {
+         Key::Char('q') | Key::Esc | Key::Ctrl('c') => return false,
+         Key::Char(' ') => self.playing = !self.playing,
+         Key::Right | Key::Char('l') => {
+            self.playing = false;
+            self.step(1);
+         }
+         Key::Left | Key::Char('h') => {
+            self.playing = false;
+            self.step(-1);
+         }
+         Key::Char('d') => {
+            self.playing = false;
+            if let Some(frame) = self.replay.first_elf_death() {
+               self.frame = frame;
+            }
+         }
+         Key::Home | Key::Char('g') => self.frame = 0,
+         Key::End | Key::Char('G') => self.frame = self.replay.len() - 1,
+         _ => {}
+      }
+
+      true
+   }
+
+   fn step(&mut self, rounds: isize) {
+      let last = self.replay.len() as isize - 1;
+
+      self.frame = (self.frame as isize + rounds).max(0).min(last) as usize;
+   }
+
+   fn draw<W: Write>(&self, output: &mut W) -> io::Result<()> {
+      let battle = self.replay.frame(self.frame);
+      let map = battle.map();
+
+      write!(output, "{}{}", clear::All, cursor::Goto(1, 1))?;
+      write!(output, "{}Round {} of {}{}  elf attack power {}  {}\r\n\r\n",
+             style::Bold, battle.rounds(), self.replay.frame(self.replay.len() - 1).rounds(), style::Reset,
+             battle.rules().elf.attack_power, if self.playing { "playing" } else { "paused" })?;
+
+      for y in map.y_range() {
+         let mut row_units = vec![];
+
+         for x in map.x_range() {
+            let loci = Loci::new(x, y);
+            match battle.units().iter().find(|unit| unit.is_alive() && unit.loci() == loci) {
+               Some(unit) => {
+                  write!(output, "{}{}{}{}{}", style::Bold, species_color(unit.species()), unit.species(), color::Fg(color::Reset), style::Reset)?;
+                  row_units.push(unit);
+               }
+               None if *map.get_loci(&loci) => write!(output, "{}#{}", color::Fg(color::LightBlack), color::Fg(color::Reset))?,
+               None => write!(output, "{}.{}", color::Fg(color::Yellow), color::Fg(color::Reset))?,
+            }
+         }
+
+         for unit in row_units {
+            write!(output, "  {}{} {}{} {:3}",
+                   species_color(unit.species()), unit.species(), self.health_bar(unit.health()), color::Fg(color::Reset), unit.health())?;
+         }
+         write!(output, "\r\n")?;
+      }
+
+      write!(output, "\r\n")?;
+      match self.replay.first_elf_death() {
+         Some(frame) => write!(output, "The first elf dies in round {}\r\n", frame)?,
+         None => write!(output, "Every elf makes it\r\n")?,
+      }
+
+      for (unit, killer) in self.replay.elf_deaths(self.frame) {
+         let (unit, killer) = (battle.units()[unit], battle.units()[killer]);
+
+         write!(output, "{}Elf at {},{} was killed by the goblin at {},{}{}\r\n",
+                color::Fg(color::Red), unit.loci().x(), unit.loci().y(), killer.loci().x(), killer.loci().y(), color::Fg(color::Reset))?;
+      }
+
+      write!(output, "\r\nspace play/pause  \u{2190}/h back  \u{2192}/l forward  d first elf death  g/G start/end  q quit\r\n")?;
+      output.flush()
+   }
+
+   fn health_bar(&self, health: isize) -> String {
+      let filled = (health.max(0) as usize * HEALTH_BAR_WIDTH).div_ceil(self.max_health as usize);
+
+      format!("{}{}", "\u{2588}".repeat(filled), "\u{2591}".repeat(HEALTH_BAR_WIDTH - filled.min(HEALTH_BAR_WIDTH)))
+   }
+}
+
+fn species_color(species: Species) -> String {
+   match species {
+      Species::Elf => color::Fg(color::Green).to_string(),
+      Species::Goblin => color::Fg(color::Red).to_string(),
+   }
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use common::*;
+use elfcode::*;
+use elfcode::inference;
+use elfcode::inference::Inference;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+   type Input = Manual;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Manual, ParseError> {
+      Manual::parse(contents)
+   }
+
+   fn part_a(&self, manual: &Manual) -> usize {
+      a(&Inference::new(&manual.steps))
+   }
+
+   fn part_b(&self, manual: &Manual) -> usize {
+      b(&Inference::new(&manual.steps), &manual.program)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![]
+   }
+}
+
+fn a(inference: &Inference) -> usize {
+   inference.candidates.iter()
+      .filter(|candidates| candidates.len() >= 3)
+      .count()
+}
+
+fn b(inference: &Inference, program: &Vec<NumericInstruction>) -> usize {
+   let op_mapping = match inference.solve() {
+      Ok(inference::Solution::Unique(op_mapping)) => op_mapping,
+      Ok(inference::Solution::Ambiguous(op_mappings)) => panic!("the manual allows {} different mappings", op_mappings.len()),
+      Err(conflict) => panic!("the manual contradicts itself: {}", conflict),
+   };
+
+   let instructions = program.iter()
+      .map(|instruction| instruction.resolve(*op_mapping.get(&instruction.op_id).unwrap()))
+      .collect();
+   let program = Program::new(None, instructions);
+
+   let mut machine = Machine::new(&program, vec![0, 0, 0, 0]);
+   machine.run()[0]
+}
@@ -1,7 +1,10 @@
 use std::fmt;
 use regex::Regex;
 
+use common::{parse_at, ParseError};
 use common::coordinates::Grid;
+use common::coordinates::GridChar;
+use common::coordinates::InfiniteGrid;
 use common::coordinates::Loci;
 use common::coordinates::OffsetLociX;
 use common::coordinates::OffsetLociY;
@@ -28,23 +31,20 @@ pub fn tick(ground: &mut Grid<Ground>) -> bool {
          match dirt {
             Ground::WaterFalling => {
                let down_y = y + 1;
-               if down_y < ground.y_max() {
-                  match ground.get(x, down_y) {
-                     Ground::Clay | Ground::WaterLocked => {
-                        // check if we have anything to fill in
-                        if (x - 1 < ground.x_min() || *ground.get(x - 1, y) != Ground::WaterFalling) ||
-                           (x + 1 >= ground.x_max() || *ground.get(x + 1, y) != Ground::WaterFalling) {
-                           acted = fill_ledge(x, y, ground) || acted;
-                        }
-
-                        //println!("{} {}", x, y);
-                     }
-                     Ground::Sand => {
-                        ground.set(x, down_y, Ground::WaterFalling);
-                        acted = true;
-                     }
-                     _ => {}
+               match ground.try_get(x, down_y) {
+                  // check if we have anything to fill in
+                  Some(Ground::Clay) | Some(Ground::WaterLocked) if
+                     ground.try_get(x - 1, y) != Some(&Ground::WaterFalling) ||
+                        ground.try_get(x + 1, y) != Some(&Ground::WaterFalling) => {
+                     acted = fill_ledge(x, y, ground) || acted;
+
+                     //println!("{} {}", x, y);
                   }
+                  Some(Ground::Sand) => {
+                     ground.set(x, down_y, Ground::WaterFalling);
+                     acted = true;
+                  }
+                  _ => {}
                }
             }
             _ => {}
@@ -55,6 +55,26 @@ pub fn tick(ground: &mut Grid<Ground>) -> bool {
    acted
 }
 
+// only the water changes, so that's all we need to draw after the first frame
+pub fn ground_color(ground: &Ground, full_render: bool) -> Option<u32> {
+   let mut water = false;
+   let color = match ground {
+      Ground::Clay => 0x6D5800FF,
+      Ground::Sand => 0xBEAF70FF,
+      _ => {
+         water = true;
+         0x5DADE2FF
+      }
+   };
+
+   if full_render || water {
+      Some(color)
+   }
+   else {
+      None
+   }
+}
+
 pub fn count_water(ground: &Grid<Ground>) -> (usize, usize) {
    let mut water_count = 0;
    let mut water_locked = 0;
@@ -92,8 +112,8 @@ fn fill_ledge(x_start: isize, y: isize, ground: &mut Grid<Ground>) -> bool {
             acted = acted || dirt == Ground::Sand;
 
             // check if we have something to stand on
-            match ground.get(x, y + 1) {
-               Ground::Clay | Ground::WaterLocked => {
+            match ground.try_get(x, y + 1) {
+               Some(Ground::Clay) | Some(Ground::WaterLocked) => {
                   min_found = x;
                }
                _ => {
@@ -121,8 +141,8 @@ fn fill_ledge(x_start: isize, y: isize, ground: &mut Grid<Ground>) -> bool {
             acted = acted || dirt == Ground::Sand;
 
             // check if we have something to stand on
-            match ground.get(x, y + 1) {
-               Ground::Clay | Ground::WaterLocked => {
+            match ground.try_get(x, y + 1) {
+               Some(Ground::Clay) | Some(Ground::WaterLocked) => {
                   max_found = x;
                }
                _ => {
@@ -152,96 +172,71 @@ fn fill_ledge(x_start: isize, y: isize, ground: &mut Grid<Ground>) -> bool {
    return acted;
 }
 
-pub fn parse_input(contents: &String) -> Grid<Ground> {
+pub fn parse_input(contents: &str) -> Result<Grid<Ground>, ParseError> {
    let re: Regex = Regex::new(r"([xy])=(\d+), ([xy])=(\d+)\.\.(\d+)").unwrap();
 
-   let mut min = Loci::max_value();
-   let mut max = Loci::new(0, 0);
-   let clay_lines: Vec<(char, isize, isize, isize)> = contents.lines()
-      .map(|row| {
-         let parsed_row = re.captures(row).unwrap();
-
-         let singular = parsed_row[1].chars().next().unwrap();
-         let singular_start = parsed_row[2].parse::<isize>().unwrap();
+   let mut ground = InfiniteGrid::new(Ground::Sand);
+   for row in contents.lines() {
+      let parsed_row = re.captures(row)
+         .ok_or_else(|| ParseError::at(contents, row, "expected a vein of clay like x=495, y=2..7"))?;
 
-         let multiple_from = parsed_row[4].parse::<isize>().unwrap();
-         let multiple_to = parsed_row[5].parse::<isize>().unwrap();
+      let singular = parsed_row[1].chars().next().unwrap();
+      let singular_start = parse_at(contents, &parsed_row[2])?;
 
-         match singular {
-            'x' => {
-               min = min.min_x(singular_start);
-               max = max.max_x(singular_start);
+      let multiple_from = parse_at(contents, &parsed_row[4])?;
+      let multiple_to = parse_at(contents, &parsed_row[5])?;
 
-               min = min.min_y(multiple_from);
-               max = max.max_y(multiple_to);
-            }
-            'y' => {
-               min = min.min_y(singular_start);
-               max = max.max_y(singular_start);
-
-               min = min.min_x(multiple_from);
-               max = max.max_x(multiple_to);
-            }
-            u => panic!("Unexpected char: {}", u)
-         }
-
-         (singular, singular_start, multiple_from, multiple_to)
-      })
-      .collect();
-
-   // make max inclusive
-   max = max.add(1, 1);
-   // make sure we have enough space for falling water on the left and right
-   min = min.sub_x(1);
-   max = max.add_x(1);
-
-   let mut ground = Grid::new_loci_offset(
-      Ground::Sand,
-      &max.sub_loci(&min),
-      &min,
-   );
-
-   for (singular, singular_start, multiple_from, multiple_to) in clay_lines {
-      let x_min;
-      let x_max;
-      let y_min;
-      let y_max;
-      match singular {
-         'x' => {
-            x_min = singular_start;
-            x_max = singular_start;
-            y_min = multiple_from;
-            y_max = multiple_to;
-         }
-         'y' => {
-            x_min = multiple_from;
-            x_max = multiple_to;
-            y_min = singular_start;
-            y_max = singular_start;
-         }
+      let (min, max) = match singular {
+         'x' => (Loci::new(singular_start, multiple_from), Loci::new(singular_start, multiple_to)),
+         'y' => (Loci::new(multiple_from, singular_start), Loci::new(multiple_to, singular_start)),
          u => panic!("Unexpected char: {}", u)
-      }
+      };
 
-      for x in x_min..=x_max {
-         for y in y_min..=y_max {
+      for x in min.x()..=max.x() {
+         for y in min.y()..=max.y() {
             ground.set(x, y, Ground::Clay);
          }
       }
    }
 
+   // trim back down to our clay
+   ground.shrink_to_fit();
+   let mut ground = ground.into_grid();
+
+   // make sure we have enough space for falling water on the left and right
+   let (x_min, x_max, y_min) = (ground.x_min(), ground.x_max(), ground.y_min());
+   ground.grow_to_include(&Loci::new(x_min - 1, y_min), Ground::Sand);
+   ground.grow_to_include(&Loci::new(x_max, y_min), Ground::Sand);
+
    // add the water
    ground.set(500, ground.y_min(), Ground::WaterFalling);
 
-   ground
+   Ok(ground)
 }
 
-impl fmt::Display for Ground {
-   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl GridChar for Ground {
+   fn from_char(c: char) -> Option<Ground> {
+      match c {
+         '#' => Some(Ground::Clay),
+         '.' => Some(Ground::Sand),
+         '|' => Some(Ground::WaterFalling),
+         '~' => Some(Ground::WaterLocked),
+         _ => None,
+      }
+   }
+
+   fn to_char(&self) -> char {
       match *self {
-         Ground::Clay => write!(f, "#"),
-         Ground::Sand => write!(f, "."),
-         Ground::WaterFalling => write!(f, "|"),
-         Ground::WaterLocked => write!(f, "~"),
+         Ground::Clay => '#',
+         Ground::Sand => '.',
+         Ground::WaterFalling => '|',
+         Ground::WaterLocked => '~',
       }
    }
 }
+
+impl fmt::Display for Ground {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{}", self.to_char())
+   }
+}
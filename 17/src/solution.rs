@@ -0,0 +1,88 @@
+use common::*;
+use common::animation::Recorder;
+use common::canvas::Image;
+use common::coordinates::Grid;
+
+use crate::shared::*;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+   type Input = Grid<Ground>;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Grid<Ground>, ParseError> {
+      parse_input(contents)
+   }
+
+   fn part_a(&self, ground: &Grid<Ground>) -> usize {
+      ab(ground).0
+   }
+
+   fn part_b(&self, ground: &Grid<Ground>) -> usize {
+      ab(ground).1
+   }
+
+   fn both(&self, ground: &Grid<Ground>) -> (usize, usize) {
+      ab(ground)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![
+         Example::file("test_input_1.txt").a(45).b(17),
+         Example::sample().a(57).b(29),
+      ]
+   }
+
+   fn render(&self, ground: &Grid<Ground>) -> Option<Image> {
+      Some(Image::render(2, &flood(ground), &|cell| ground_color(cell, true)))
+   }
+
+   fn animate(&self, initial_ground: &Grid<Ground>, recorder: &mut Recorder) -> bool {
+      let colorizer = |cell: &Ground| ground_color(cell, true);
+      let mut ground = initial_ground.clone();
+
+      recorder.tick(1, &ground, &colorizer);
+      while tick(&mut ground) {
+         recorder.tick(1, &ground, &colorizer);
+      }
+      recorder.finish(1, &ground, &colorizer);
+
+      true
+   }
+}
+
+fn ab(initial_ground: &Grid<Ground>) -> (usize, usize) {
+   count_water(&flood(initial_ground))
+}
+
+fn flood(initial_ground: &Grid<Ground>) -> Grid<Ground> {
+   let mut ground = initial_ground.clone();
+
+   while tick(&mut ground) {
+//      println!("Step");
+//      print_subset(&ground);
+   }
+
+   ground
+}
+
+// only print the ground that has water in it
+//fn print_subset(ground: &Grid<Ground>) {
+//   for y in ground.y_range() {
+//      // found water
+//      let mut found_water= false;
+//      for x in ground.x_range() {
+//         let dirt = ground.get(x, y);
+//         found_water = found_water || *dirt == Ground::WaterFalling || *dirt == Ground::WaterLocked;
+//
+//         print!("{} ", dirt);
+//      }
+//      println!();
+//
+//      if !found_water {
+//         return;
+//      }
+//   }
+//}
\ No newline at end of file
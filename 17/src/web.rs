@@ -13,10 +13,13 @@ pub use common::wasm::*;
 use crate::shared::*;
 
 mod shared;
+mod solution;
+
+pub use crate::solution::Day17;
 
 #[wasm_bindgen]
 pub fn new_ground(contents: String) -> *mut Grid<Ground> {
-   Box::into_raw(Box::new(parse_input(&contents)))
+   Box::into_raw(Box::new(parse_input(&contents).unwrap_or_else(|e| panic!("{}", e))))
 }
 
 #[wasm_bindgen]
@@ -72,22 +75,5 @@ pub fn render_ground(pixel_size: usize, img_data_pointer: *mut u32, ground: *mut
       (ground, img_data)
    };
 
-   render_grid(pixel_size, img_data, &ground, &|cell| {
-      let mut water = false;
-      let color = match cell {
-         Ground::Clay => 0x6d5800FF,
-         Ground::Sand => 0xbeaf70FF,
-         _ => {
-            water = true;
-            0x5dade2FF
-         }
-      };
-
-      if full_render || water {
-         Some(color)
-      }
-      else {
-         None
-      }
-   });
+   render_grid(pixel_size, img_data, ground, &|cell| ground_color(cell, full_render));
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
+use common::ParseError;
 use common::coordinates::Grid;
+use common::coordinates::GridChar;
+use common::coordinates::Loci;
 use common::coordinates::OffsetLociX;
 use common::coordinates::OffsetLociY;
 
@@ -45,47 +48,49 @@ fn count_adjacent(x: isize, y: isize, area: &Grid<Acre>) -> (usize, usize, usize
    let mut tree_count = 0;
    let mut lumberyard_count = 0;
 
-   let min_x = area.x_min().max(x - 1);
-   let max_x = (area.x_max() - 1).min(x + 1);
-   let min_y = area.y_min().max(y - 1);
-   let max_y = (area.y_max() - 1).min(y + 1);
-
-   for yi in min_y..=max_y {
-      for xi in min_x..=max_x {
-         if x != xi || y != yi {
-            match area.get(xi, yi) {
-               Acre::Open => open_count += 1,
-               Acre::Tree => tree_count += 1,
-               Acre::Lumberyard => lumberyard_count += 1,
-            }
-         }
+   for (_, acre) in area.neighborhood(Loci::new(x, y).adjacent()) {
+      match acre {
+         Acre::Open => open_count += 1,
+         Acre::Tree => tree_count += 1,
+         Acre::Lumberyard => lumberyard_count += 1,
       }
    }
 
    (open_count, tree_count, lumberyard_count)
 }
 
-pub fn parse_input(contents: &String) -> Grid<Acre> {
-   let lines: Vec<Vec<Acre>> = contents.lines()
-      .map(|row| {
-         row.chars()
-            .map(|c| match c {
-               '|' => Acre::Tree,
-               '#' => Acre::Lumberyard,
-               '.' => Acre::Open,
-               u => panic!("Unexpected char: {}", u)
-            })
-            .collect()
-      })
-      .collect();
+pub fn acre_color(acre: &Acre) -> Option<u32> {
+   Some(match acre {
+      Acre::Open => 0xBEAF70FF,
+      Acre::Tree => 0x298210FF,
+      Acre::Lumberyard => 0x6B4B0BFF,
+   })
+}
 
-   let mut area = Grid::new(Acre::Open, lines[0].len(), lines.len());
+pub fn parse_input(contents: &str) -> Result<Grid<Acre>, ParseError> {
+   let width = contents.lines().next().map(|line| line.chars().count()).unwrap_or(0);
+   if let Some(y) = contents.lines().position(|line| line.chars().count() != width) {
+      return Err(ParseError::new(y + 1, 1, format!("every row should be {} acres wide", width)));
+   }
 
-   for y in area.y_range() {
-      for x in area.x_range() {
-         area.set(x, y, lines[y as usize][x as usize])
+   Grid::parse_ascii(contents, Acre::Open)
+}
+
+impl GridChar for Acre {
+   fn from_char(c: char) -> Option<Acre> {
+      match c {
+         '|' => Some(Acre::Tree),
+         '#' => Some(Acre::Lumberyard),
+         '.' => Some(Acre::Open),
+         _ => None,
       }
    }
 
-   area
+   fn to_char(&self) -> char {
+      match *self {
+         Acre::Tree => '|',
+         Acre::Lumberyard => '#',
+         Acre::Open => '.',
+      }
+   }
 }
\ No newline at end of file
@@ -13,10 +13,13 @@ use crate::shared::*;
 pub use common::wasm::*;
 
 mod shared;
+mod solution;
+
+pub use crate::solution::Day18;
 
 #[wasm_bindgen]
 pub fn new_lumberyard(contents: String) -> *mut Grid<Acre> {
-   Box::into_raw(Box::new(parse_input(&contents)))
+   Box::into_raw(Box::new(parse_input(&contents).unwrap_or_else(|e| panic!("{}", e))))
 }
 
 #[wasm_bindgen]
@@ -62,11 +65,5 @@ pub fn render_lumberyard(pixel_size: usize, img_data_pointer: *mut u32, lumberya
       (lumberyard, img_data)
    };
 
-   render_grid(pixel_size, img_data, &lumberyard, &|cell| {
-      Some(match cell {
-         Acre::Open => 0xbeaf70FF,
-         Acre::Tree => 0x298210FF,
-         Acre::Lumberyard =>0x6b4b0bFF,
-      })
-   });
+   render_grid(pixel_size, img_data, lumberyard, &acre_color);
 }
\ No newline at end of file
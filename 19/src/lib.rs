@@ -0,0 +1,40 @@
+use common::*;
+use elfcode::*;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+   type Input = Program;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Program, ParseError> {
+      Program::parse(contents)
+   }
+
+   fn part_a(&self, program: &Program) -> usize {
+      a(program)
+   }
+
+   fn part_b(&self, program: &Program) -> usize {
+      b(program)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![Example::sample().only(Part::A).a(6)]
+   }
+}
+
+fn a(program: &Program) -> usize {
+   run_program(vec![0, 0, 0, 0, 0, 0], program)
+}
+
+fn b(program: &Program) -> usize {
+   run_program(vec![1, 0, 0, 0, 0, 0], program)
+}
+
+fn run_program(initial_registers: Vec<usize>, program: &Program) -> usize {
+   let mut machine = Machine::optimized(program, initial_registers);
+
+   return machine.run()[0];
+}
@@ -0,0 +1,99 @@
+use common::*;
+use std::collections::BTreeSet;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+   type Input = Vec<String>;
+   type A = i32;
+   type B = String;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Vec<String>, ParseError> {
+      Ok(contents.split_whitespace()
+         .map(|id| id.to_string())
+         .collect())
+   }
+
+   fn part_a(&self, ids: &Vec<String>) -> i32 {
+      a(ids)
+   }
+
+   fn part_b(&self, ids: &Vec<String>) -> String {
+      b(ids)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![]
+   }
+}
+
+fn a(ids: &Vec<String>) -> i32 {
+   let mut two = 0;
+   let mut three = 0;
+   for id in ids {
+      let (id_two, id_three) = id_letter_count(id);
+
+      if id_two > 0 {
+         two += 1
+      }
+      if id_three > 0 {
+         three += 1
+      }
+   }
+
+   two * three
+}
+
+fn b(ids: &Vec<String>) -> String {
+   for id in ids {
+      let found = ids.iter()
+         .map(|other| id_diff(id, other))
+         .filter(|result| result.0 == 1)
+         .map(|result| result.1)
+         .next();
+
+      if let Some(found) = found {
+         return found;
+      }
+   }
+
+   panic!("no two ids differ by a single letter")
+}
+
+fn id_letter_count(id: &str) -> (i32, i32) {
+   let mut seen = BTreeSet::new();
+
+   let mut two = 0;
+   let mut three = 0;
+   for (i, c) in id.chars().enumerate() {
+      if !seen.contains(&c) {
+         let count = id[i..].chars()
+            .filter(|inner_c| *inner_c == c)
+            .count();
+
+         match count {
+            2 => two += 1,
+            3 => three += 1,
+            _ => (),
+         }
+
+         seen.insert(c);
+      }
+   }
+
+   return (two, three);
+}
+
+fn id_diff(left: &str, right: &str) -> (i32, String) {
+   let mut diff = 0;
+   let mut same = String::new();
+   for (i, c) in left.chars().enumerate() {
+      if c != right.chars().nth(i).unwrap() {
+         diff += 1;
+      } else {
+         same.push(c);
+      }
+   }
+
+   return (diff, same);
+}
\ No newline at end of file
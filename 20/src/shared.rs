@@ -5,10 +5,13 @@ use std::collections::BTreeSet;
 use std::collections::btree_set;
 use std::mem;
 
+use common::ParseError;
 use common::coordinates::Grid;
+use common::coordinates::InfiniteGrid;
 use common::coordinates::Loci;
 use common::coordinates::OffsetLociX;
 use common::coordinates::OffsetLociY;
+use common::search::Search;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum MapFeature {
@@ -93,38 +96,6 @@ impl Path {
          }
       }
    }
-
-   // calculate the max distance (up/down, left/right)
-   fn max_distance(&self) -> (usize, usize) {
-      match self {
-         Path::Segments(segments) => {
-            segments.iter().fold((0, 0), |(ud, lr), p| {
-               let (p_ud, p_lr) = p.max_distance();
-
-               (ud + p_ud, lr + p_lr)
-            })
-         }
-         Path::Branch(branches) => {
-            branches.iter().fold((0, 0), |(ud, lr), p| {
-               let (p_ud, p_lr) = p.max_distance();
-
-               (ud.max(p_ud), lr.max(p_lr))
-            })
-         }
-         Path::Static(path) => {
-            let (up_down, left_right) = path.iter().fold((0 as isize, 0 as isize), |(ud, lr), direction| {
-               match direction {
-                  Direction::North => (ud + 1, lr),
-                  Direction::East => (ud, lr + 1),
-                  Direction::South => (ud - 1, lr),
-                  Direction::West => (ud, lr - 1),
-               }
-            });
-
-            (up_down.abs() as usize, left_right.abs() as usize)
-         }
-      }
-   }
 }
 
 impl fmt::Display for Path {
@@ -326,22 +297,37 @@ impl PathIterator for EmptyPathIterator {
 }
 
 
-pub fn parse_input(contents: &String) -> Path {
-   let mut bytes = contents.chars().skip(1).peekable();
+pub fn parse_input(contents: &str) -> Result<Path, ParseError> {
+   let route = contents.trim();
+   if !route.starts_with('^') {
+      return Err(ParseError::at(contents, route, "expected our route to start with ^"));
+   }
+
+   let mut bytes = contents.char_indices()
+      .skip_while(|(_, c)| c.is_whitespace())
+      .skip(1)
+      .peekable();
+
+   let path = parse_path(contents, &mut bytes)?;
 
-   let path = parse_path(&mut bytes);
+   match bytes.next() {
+      Some((_, '$')) => Ok(path),
+      found => Err(unexpected(contents, found, "expected our route to end with $")),
+   }
+}
 
-   path
+// points at whatever we found instead, or the end of our input
+fn unexpected(contents: &str, found: Option<(usize, char)>, reason: &str) -> ParseError {
+   match found {
+      Some((i, c)) => ParseError::at(contents, &contents[i..], format!("unexpected {:?}, {}", c, reason)),
+      None => ParseError::at(contents, &contents[contents.trim_end().len()..], reason),
+   }
 }
 
 pub fn build_map(path: &Path) -> (Loci, Grid<MapFeature>) {
-   let (max_up_down, max_left_right) = path.max_distance();
-   // multiply by 2 to give us room for our doors between rooms
-   let center = Loci::new((max_left_right as isize + 2) * 2, (max_up_down as isize + 2) * 2);
-   let width = (center.x() as usize) * 2;
-   let height = (center.y() as usize) * 2;
+   let center = Loci::new(0, 0);
 
-   let mut map = Grid::new(MapFeature::Wall, width, height);
+   let mut map = InfiniteGrid::new(MapFeature::Wall);
 
    // always start in a room
    map.set_loci(&center, MapFeature::Room);
@@ -351,107 +337,49 @@ pub fn build_map(path: &Path) -> (Loci, Grid<MapFeature>) {
       map.set_loci(&map_move[1].0, map_move[1].1);
    }
 
-   (center, prune_map(map))
-}
-
-fn prune_map(old_map: Grid<MapFeature>) -> Grid<MapFeature> {
-   let mut min = Loci::max_value();
-   let mut max = Loci::min_value();
-
-   for (loci, feature) in old_map.enumerate() {
-      if *feature != MapFeature::Wall {
-         min = min.min_x(loci.x()).min_y(loci.y());
-         max = max.max_x(loci.x()).max_y(loci.y());
-      }
-   }
+   map.shrink_to_fit();
+   let mut map = map.into_grid();
 
    // add some outer walls back in
-   min = min.sub(1, 1);
-   max = max.add(2, 2);
-
-   let mut map = Grid::new_loci_offset(MapFeature::Wall, &max.sub_loci(&min), &min);
-
-   for y in map.y_range() {
-      for x in map.x_range() {
-         map.set(x, y, *old_map.get(x, y))
-      }
-   }
+   let (min, max) = (Loci::new(map.x_min() - 1, map.y_min() - 1), Loci::new(map.x_max(), map.y_max()));
+   map.grow_to_include(&min, MapFeature::Wall);
+   map.grow_to_include(&max, MapFeature::Wall);
 
-   map
+   (center, map)
 }
 
 pub fn ab(start: &Loci, map: &Grid<MapFeature>) -> (usize, usize) {
-   let mut distance_grid = Grid::new_offset(
-      usize::max_value(),
-      map.width(),
-      map.height(),
-      map.x_min(),
-      map.y_min(),
-   );
-   distance_grid.set_loci(start, 0);
-
-   let mut locations = BTreeSet::new();
-   locations.insert(start.clone());
-
-   let mut max = 0;
-   let mut count = 0;
-   while !locations.is_empty() {
-      let mut next_locations: BTreeSet<Loci> = BTreeSet::new();
-
-      for location in locations.iter() {
-         let distance = distance_grid.get_loci(location);
-
-         let neighbors: Vec<Loci> = location.neighbors().iter()
-            .filter_map(|neighbor| {
-               if *map.get_loci(neighbor) == MapFeature::Door {
-                  let mut double = neighbor.sub_loci(location);
-                  double = double.add_loci(&double);
-
-                  // move into the room
-                  Some(location.add_loci(&double))
-               } else {
-                  None
-               }
-            })
-            .collect();
-
-         let neighbor_distance = distance + 1;
-         for neighbor in neighbors {
-            let last_distance = *distance_grid.get_loci(&neighbor);
+   let rooms = Search::new(*start, |location: &Loci| {
+      let location = *location;
 
-            if neighbor_distance < last_distance {
-               max = max.max(neighbor_distance);
-               if neighbor_distance >= 1000 {
-                  count += 1;
-               }
-
-               distance_grid.set_loci(&neighbor, neighbor_distance);
-
-               next_locations.insert(neighbor);
-            }
-         }
-      }
+      location.orthogonal()
+         .filter(|neighbor| *map.get_loci(neighbor) == MapFeature::Door)
+         // move through the door into the room
+         .map(move |door| door.add_loci(&door.sub_loci(&location)))
+   }).bfs();
 
-      locations = next_locations;
-   }
+   let max = rooms.distances().values().cloned().max().unwrap_or(0);
+   let count = rooms.distances().values()
+      .filter(|distance| **distance >= 1000)
+      .count();
 
    (max, count)
 }
 
-fn parse_path<I>(input_iter: &mut Peekable<I>) -> Path
-   where I: Iterator<Item=char> {
+fn parse_path<I>(contents: &str, input_iter: &mut Peekable<I>) -> Result<Path, ParseError>
+   where I: Iterator<Item=(usize, char)> {
    let mut segments: Vec<Path> = vec![];
    loop {
-      match input_iter.peek().unwrap() {
-         '(' => segments.push(parse_branch(input_iter)),
-         'N' | 'E' | 'S' | 'W' => {
+      match input_iter.peek().map(|(_, c)| *c) {
+         Some('(') => segments.push(parse_branch(contents, input_iter)?),
+         Some('N') | Some('E') | Some('S') | Some('W') => {
             segments.push(parse_static(input_iter))
          }
          _ => break,
       }
    }
 
-   if segments.len() == 0 {
+   Ok(if segments.len() == 0 {
       // if we have an empty path represent it as static
       Path::Static(vec![])
    }
@@ -460,31 +388,28 @@ fn parse_path<I>(input_iter: &mut Peekable<I>) -> Path
       segments.swap_remove(0)
    } else {
       Path::Segments(segments)
-   }
+   })
 }
 
-fn parse_branch<I>(input_iter: &mut Peekable<I>) -> Path
-   where I: Iterator<Item=char> {
+fn parse_branch<I>(contents: &str, input_iter: &mut Peekable<I>) -> Result<Path, ParseError>
+   where I: Iterator<Item=(usize, char)> {
    let mut branches: Vec<Path> = vec![];
 
    // consume the first (
    input_iter.next().unwrap();
 
    // consume the first path
-   branches.push(parse_path(input_iter));
+   branches.push(parse_path(contents, input_iter)?);
 
    loop {
-      match input_iter.peek().unwrap() {
-         ')' => break,
-         '|' => {
+      match input_iter.peek() {
+         Some((_, ')')) => break,
+         Some((_, '|')) => {
             // consume a break, then the next path
             input_iter.next();
-            branches.push(parse_path(input_iter));
-         }
-         _ => {
-            // this shouldn't happen
-            break;
+            branches.push(parse_path(contents, input_iter)?);
          }
+         found => return Err(unexpected(contents, found.cloned(), "expected a | or the ) that ends this branch")),
       };
    }
 
@@ -492,18 +417,18 @@ fn parse_branch<I>(input_iter: &mut Peekable<I>) -> Path
    input_iter.next();
 
    // if we only have one, don't bother with wrapping it
-   if branches.len() == 1 {
+   Ok(if branches.len() == 1 {
       branches.swap_remove(0)
    } else {
       Path::Branch(branches)
-   }
+   })
 }
 
 fn parse_static<I>(input_iter: &mut Peekable<I>) -> Path
-   where I: Iterator<Item=char> {
+   where I: Iterator<Item=(usize, char)> {
    let mut path = vec![];
    loop {
-      match input_iter.peek() {
+      match input_iter.peek().map(|(_, c)| *c) {
          Some('N') => path.push(Direction::North),
          Some('E') => path.push(Direction::East),
          Some('S') => path.push(Direction::South),
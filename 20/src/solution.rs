@@ -0,0 +1,34 @@
+use common::*;
+use common::coordinates::Grid;
+use common::coordinates::Loci;
+
+use crate::shared::*;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+   // where we start and the map of the facility
+   type Input = (Loci, Grid<MapFeature>);
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(Loci, Grid<MapFeature>), ParseError> {
+      Ok(build_map(&parse_input(contents)?))
+   }
+
+   fn part_a(&self, input: &(Loci, Grid<MapFeature>)) -> usize {
+      self.both(input).0
+   }
+
+   fn part_b(&self, input: &(Loci, Grid<MapFeature>)) -> usize {
+      self.both(input).1
+   }
+
+   fn both(&self, (start, map): &(Loci, Grid<MapFeature>)) -> (usize, usize) {
+      ab(start, map)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      Example::numbered("test_input_{}.txt", Part::A, vec![3, 10, 18, 23, 31, 6])
+   }
+}
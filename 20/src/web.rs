@@ -15,6 +15,9 @@ pub use common::wasm::*;
 use crate::shared::*;
 
 mod shared;
+mod solution;
+
+pub use crate::solution::Day20;
 
 const MAX_BRIGHTNESS: u32 = 0xDD;
 
@@ -92,8 +95,8 @@ pub fn render_distance(locations_pointer: *mut (BTreeSet<Loci>, Grid<usize>),
          let distance = distance_grid.get_loci(location);
 
          let neighbor_distance = distance + 1;
-         for door in location.neighbors().iter() {
-            if *map.get_loci(door) == MapFeature::Door {
+         for door in location.orthogonal() {
+            if *map.get_loci(&door) == MapFeature::Door {
                let direction = door.sub_loci(location);
                let room = door.add_loci(&direction);
 
@@ -111,7 +114,7 @@ pub fn render_distance(locations_pointer: *mut (BTreeSet<Loci>, Grid<usize>),
                   let blue = gray_scale << 8;
 
                   let color = 0x000000FF | red | green | blue;
-                  for loci in [*door, room].iter() {
+                  for loci in [door, room].iter() {
                      set_grid_square(
                         loci.x(),
                         loci.y(),
@@ -197,7 +200,7 @@ pub fn delete_locations(locations_pointer: *mut (BTreeSet<Loci>, Grid<usize>)) {
 
 #[wasm_bindgen]
 pub fn new_path(contents: String) -> *mut Path {
-   Box::into_raw(Box::new(parse_input(&contents)))
+   Box::into_raw(Box::new(parse_input(&contents).unwrap_or_else(|e| panic!("{}", e))))
 }
 
 #[wasm_bindgen]
@@ -0,0 +1,86 @@
+use std::collections::BTreeSet;
+
+use common::*;
+use elfcode::*;
+
+pub struct Day21;
+
+/// Our program along with where it checks register 0 against the value that would halt it
+pub struct Device {
+   program: Program,
+   check_ip: usize,
+   check_register: usize,
+}
+
+impl Solution for Day21 {
+   type Input = Device;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Device, ParseError> {
+      let program = Program::parse(contents)?;
+
+      // the only place register 0 is read is when it's checked against the value that would halt us
+      let (check_ip, check_register) = program.instructions.iter().enumerate()
+         .find(|(_, instruction)| instruction.op_code == OpCode::Eqrr && (instruction.a == 0 || instruction.b == 0))
+         .map(|(ip, instruction)| (ip, if instruction.a == 0 { instruction.b } else { instruction.a }))
+         .ok_or_else(|| ParseError::new(1, 1, "expected an eqrr checking register 0 against another register"))?;
+
+      Ok(Device {
+         program,
+         check_ip,
+         check_register,
+      })
+   }
+
+   fn part_a(&self, device: &Device) -> usize {
+      a(device)
+   }
+
+   fn part_b(&self, device: &Device) -> usize {
+      b(device)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![]
+   }
+}
+
+// the first value we check against halts us the soonest
+fn a(device: &Device) -> usize {
+   let mut machine = Machine::optimized(&device.program, vec![0; REGISTERS]);
+   while machine.ip() != device.check_ip {
+      if !machine.step() {
+         panic!("our program halted before it checked register 0");
+      }
+   }
+
+   machine.registers()[device.check_register]
+}
+
+fn b(device: &Device) -> usize {
+   /*
+      Strategy:
+
+      Basically we're searching for a cycle in the output, once we find one. The result right before that
+      should be our max
+   */
+   let mut machine = Machine::optimized(&device.program, vec![0; REGISTERS]);
+   let mut seen = BTreeSet::new();
+   let mut last = 0;
+   loop {
+      if machine.ip() == device.check_ip {
+         let value = machine.registers()[device.check_register];
+         if !seen.insert(value) {
+            // return the value right before our cycle
+            return last;
+         }
+
+         last = value;
+      }
+
+      if !machine.step() {
+         panic!("our program halted after checking {} values, before any of them repeated", seen.len());
+      }
+   }
+}
\ No newline at end of file
@@ -0,0 +1,264 @@
+use std::fmt;
+use regex::Regex;
+
+use common::*;
+use common::coordinates::Grid;
+use common::coordinates::Loci;
+use common::coordinates::OffsetLociX;
+use common::coordinates::OffsetLociY;
+use common::search::Search;
+
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Region {
+   region_type: RegionType,
+   geologic_index: isize,
+   erosion_level: isize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum RegionType {
+   Rocky,
+   Wet,
+   Narrow,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+enum Tool {
+   Torch = 0,
+   ClimbingGear = 1,
+   Neither = 2,
+}
+
+const TOOLS: [Tool; 3] = [
+   Tool::Torch,
+   Tool::ClimbingGear,
+   Tool::Neither,
+];
+
+pub struct Day22;
+
+impl Solution for Day22 {
+   // our target and the cave leading to it
+   type Input = (Loci, Grid<Region>);
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(Loci, Grid<Region>), ParseError> {
+      let depth_re: Regex = Regex::new(r"depth: (\d+)").unwrap();
+      let target_re: Regex = Regex::new(r"target: (\d+),(\d+)").unwrap();
+
+      let mut lines = contents.lines();
+
+      let depth = lines.next()
+         .and_then(|row| depth_re.captures(row))
+         .ok_or_else(|| ParseError::new(1, 1, "expected our depth like depth: 510"))
+         .and_then(|captures| parse_at(contents, &captures[1]))?;
+
+      let target: Loci = lines.next()
+         .and_then(|row| target_re.captures(row))
+         .ok_or_else(|| ParseError::new(2, 1, "expected our target like target: 10,10"))
+         .and_then(|captures| {
+            Ok(Loci::new(parse_at(contents, &captures[1])?, parse_at(contents, &captures[2])?))
+         })?;
+
+      let cave = build_cave(depth, &target);
+
+      //cave.print();
+
+      Ok((target, cave))
+   }
+
+   fn part_a(&self, (target, cave): &(Loci, Grid<Region>)) -> usize {
+      a(target, cave)
+   }
+
+   fn part_b(&self, (target, cave): &(Loci, Grid<Region>)) -> usize {
+      b(target, cave)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      let mut examples = Example::numbered("test_input_{}.txt", Part::B, vec![18]);
+      examples.push(Example::sample().a(114).b(45));
+
+      examples
+   }
+}
+
+fn a(target: &Loci, cave: &Grid<Region>) -> usize {
+   let mut risk = 0;
+
+   for y in 0..=target.y() {
+      for x in 0..=target.x() {
+         risk += cave.get(x, y).risk_level();
+      }
+   }
+
+   risk
+}
+
+fn b(target: &Loci, cave: &Grid<Region>) -> usize {
+   find_shortest_path(target, cave).unwrap()
+}
+
+// A* Search over where we are and what we're holding
+fn find_shortest_path(target: &Loci, cave: &Grid<Region>) -> Option<usize> {
+   let start = (Loci::new(0, 0), Tool::Torch);
+   let goal = target.clone();
+
+   let heuristic_cost_estimate = |(location, tool): &(Loci, Tool)| -> usize {
+      let mut tool_tax = 0;
+      if *tool == Tool::ClimbingGear {
+         tool_tax += 7;
+      }
+
+      location.distance(&goal) + tool_tax
+   };
+
+   let paths = Search::new(start, |(location, tool): &(Loci, Tool)| {
+      let (location, tool) = (*location, *tool);
+      let current_region = cave.get_loci(&location);
+
+      // get our possible moves
+      let moves = location.orthogonal().within(cave)
+         .filter(move |neighbor| cave.get_loci(neighbor).is_tool_valid(&tool))
+         .map(move |neighbor| (neighbor, tool));
+
+      // get our possible tool changes
+      let tool_changes = TOOLS.iter()
+         .filter(move |other| **other != tool && current_region.is_tool_valid(other))
+         .map(move |other| (location, *other));
+
+      moves.chain(tool_changes)
+   })
+      .cost(|(_, from_tool), (_, to_tool)| if from_tool == to_tool { 1 } else { 7 })
+      .heuristic(heuristic_cost_estimate)
+      .until(|(location, _)| *location == goal)
+      .astar();
+
+   paths.goal().map(|state| {
+      // SURE IF WE'RE AT THE TARGET OUR TORCH IS EQUIPPED
+      let mut result = paths.distance(state).unwrap();
+      if state.1 != Tool::Torch {
+         result += 7;
+      }
+
+      result
+   })
+}
+
+fn build_cave(depth: isize, target: &Loci) -> Grid<Region> {
+   let dimensions = (target.x() as usize * 2).max(target.y() as usize * 2).max(10);
+   let mut cave = Grid::new(
+      Region { region_type: RegionType::Rocky, geologic_index: 0, erosion_level: 0 },
+      dimensions.min(150),
+      dimensions.min(900),
+   );
+
+   for y in cave.y_range() {
+      for x in cave.x_range() {
+         let geologic_index: isize;
+         if (x == 0 && y == 0) || (x == target.x() && y == target.y()) {
+            //entrance or target
+            geologic_index = 0;
+         } else if y == 0 {
+            geologic_index = x * 16807;
+         } else if x == 0 {
+            geologic_index = y * 48271;
+         } else {
+            geologic_index = cave.get(x - 1, y).erosion_level * cave.get(x, y - 1).erosion_level;
+         }
+
+         let erosion_level = (geologic_index + depth) % 20183;
+
+         let region_type = match erosion_level % 3 {
+            0 => RegionType::Rocky,
+            1 => RegionType::Wet,
+            _ => RegionType::Narrow,
+         };
+
+         cave.set(x, y, Region {
+            region_type,
+            geologic_index,
+            erosion_level,
+         })
+      }
+   }
+
+   cave
+}
+
+impl Region {
+   fn risk_level(&self) -> usize {
+      match self.region_type {
+         RegionType::Rocky => 0,
+         RegionType::Wet => 1,
+         RegionType::Narrow => 2,
+      }
+   }
+
+   fn is_tool_valid(&self, tool: &Tool) -> bool {
+      self.region_type.is_tool_valid(tool)
+   }
+}
+
+impl RegionType {
+   fn _simple_string(&self) -> &str {
+      match *self {
+         RegionType::Rocky => ".",
+         RegionType::Wet => "=",
+         RegionType::Narrow => "|",
+      }
+   }
+
+   fn is_tool_valid(&self, tool: &Tool) -> bool {
+      match *self {
+         RegionType::Rocky => {
+            if *tool == Tool::Neither {
+               // You cannot use neither (you'll likely slip and fall).
+               return false;
+            }
+         }
+         RegionType::Wet => {
+            if *tool == Tool::Torch {
+               // if it gets wet, you won't have a light source
+               return false;
+            }
+         }
+         RegionType::Narrow => {
+            if *tool == Tool::ClimbingGear {
+               // You cannot use the climbing gear (it's too bulky to fit)
+               return false;
+            }
+         }
+      }
+
+      return true;
+   }
+}
+
+impl fmt::Display for Region {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      self.region_type.fmt(f)
+   }
+}
+
+impl fmt::Display for RegionType {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         RegionType::Rocky => write!(f, "\u{001B}[30m.\u{001B}[0m"),
+         RegionType::Wet => write!(f, "\u{001B}[34m=\u{001B}[0m"),
+         RegionType::Narrow => write!(f, "\u{001B}[31m|\u{001B}[0m"),
+      }
+   }
+}
+
+impl fmt::Display for Tool {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         Tool::Torch => write!(f, "T"),
+         Tool::ClimbingGear => write!(f, "C"),
+         Tool::Neither => write!(f, " "),
+      }
+   }
+}
\ No newline at end of file
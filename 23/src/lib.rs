@@ -0,0 +1,182 @@
+use regex::Regex;
+use std::option::Option::{Some, None};
+use std::cmp::Ordering;
+
+use priority_queue::PriorityQueue;
+
+use common::*;
+use common::geometry::{Bounds, Point};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NanoBot {
+   location: Point<3>,
+   radius: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct RegionScore {
+   potential_bots: usize,
+   zero_distance: usize,
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+   type Input = Vec<NanoBot>;
+   type A = isize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Vec<NanoBot>, ParseError> {
+      let re: Regex = Regex::new(r"pos=<([-\d]+),([-\d]+),([-\d]+)>, r=([-\d]+)").unwrap();
+
+      contents.lines()
+         .map(|row| {
+            let captures = re.captures(row)
+               .ok_or_else(|| ParseError::at(contents, row, "expected a nanobot like pos=<0,0,0>, r=4"))?;
+
+            let values = captures.iter().skip(1).take(3)
+               .map(|capture| parse_at(contents, capture.unwrap().as_str()))
+               .collect::<Result<Vec<isize>, _>>()?;
+
+            Ok(NanoBot {
+               location: Point::new([values[0], values[1], values[2]]),
+               radius: parse_at(contents, &captures[4])?,
+            })
+         })
+         .collect()
+   }
+
+   fn part_a(&self, nanobots: &Vec<NanoBot>) -> isize {
+      a(nanobots)
+   }
+
+   fn part_b(&self, nanobots: &Vec<NanoBot>) -> usize {
+      b(nanobots)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      let mut examples = Example::numbered("test_input_{}.txt", Part::B, vec![36]);
+      examples.push(Example::sample().only(Part::A).a(7));
+
+      examples
+   }
+}
+
+fn a(nanobots: &Vec<NanoBot>) -> isize {
+   let mut max_r = 0;
+   let mut max: NanoBot = NanoBot { location: Point::origin(), radius: 0 };
+
+   for bot in nanobots.iter() {
+      if bot.radius > max_r {
+         max_r = bot.radius;
+         max = bot.clone();
+      }
+   }
+
+   let mut in_range = 0;
+   for bot in nanobots.iter() {
+      if max.distance(bot) <= max.radius {
+         in_range += 1;
+      }
+   }
+
+   in_range
+}
+
+fn b(nanobots: &[NanoBot]) -> usize {
+   let mut min = Point::max_value();
+   let mut max = Point::min_value();
+
+   for bot in nanobots.iter() {
+      min = min.min(&bot.location);
+      max = max.max(&bot.location);
+   }
+
+   let region = Bounds::new(min, max);
+
+   find_closest(nanobots, &region).unwrap().distance(&Point::origin())
+}
+
+fn find_closest(nanobots: &[NanoBot], entire_region: &Bounds<3>) -> Option<Point<3>> {
+   let mut candidates: PriorityQueue<Bounds<3>, RegionScore> = PriorityQueue::new();
+   candidates.push(*entire_region, RegionScore::new(entire_region, nanobots));
+
+   while !candidates.is_empty() {
+      //let mut i = 0;
+      //for (region, score) in candidates.clone().into_sorted_iter() {
+      //   if i > 20 {
+      //      break;
+      //   }
+      //   println!("{:?} {:?} ", region.volume(), score);
+      //   i += 1;
+      //}
+      //println!();
+
+      let (region, _) = candidates.pop().unwrap();
+
+      if region.volume() == 1 {
+         return Some(*region.min());
+      }
+
+      for split_region in region.split() {
+         candidates.push(split_region, RegionScore::new(&split_region, nanobots));
+      }
+   }
+
+   None
+}
+
+impl NanoBot {
+   fn distance(&self, other: &NanoBot) -> usize {
+      self.location.distance(&other.location)
+   }
+}
+
+impl RegionScore {
+   fn new(region: &Bounds<3>, nanobots: &[NanoBot]) -> RegionScore {
+      RegionScore {
+         potential_bots: potential_bots(region, nanobots),
+         zero_distance: zero_distance(region),
+      }
+   }
+}
+
+fn potential_bots(region: &Bounds<3>, nanobots: &[NanoBot]) -> usize {
+   let center = region.mid();
+
+   // if this divides evenly our center is offset, so give us some more room
+   let tolerance: usize = (0..3)
+      .map(|axis| (region.len(axis) / 2) + if region.len(axis).is_multiple_of(2) { 1 } else { 0 })
+      .sum();
+
+   let mut in_range = 0;
+   for bot in nanobots.iter() {
+      if center.distance(&bot.location) <= bot.radius + tolerance {
+         in_range += 1;
+      }
+   }
+
+   in_range
+}
+
+fn zero_distance(region: &Bounds<3>) -> usize {
+   region.corners().iter()
+      .map(|corner| corner.distance(&Point::origin()))
+      .min()
+      .unwrap()
+}
+
+impl Ord for RegionScore {
+   fn cmp(&self, other: &RegionScore) -> Ordering {
+      self.potential_bots.cmp(&other.potential_bots)
+         .then_with(|| {
+            self.zero_distance.cmp(&other.zero_distance)
+         })
+   }
+}
+
+impl PartialOrd for RegionScore {
+   fn partial_cmp(&self, other: &RegionScore) -> Option<Ordering> {
+      Some(self.cmp(other))
+   }
+}
\ No newline at end of file
@@ -0,0 +1,560 @@
+use regex::Regex;
+use std::fmt;
+use std::option::Option::{Some, None};
+use std::cmp::Ordering;
+use std::cell::RefCell;
+
+use common::*;
+use common::animation::Recorder;
+use common::coordinates::Grid;
+use common::threshold::min_winning;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Attack {
+   Fire,
+   Slashing,
+   Bludgeoning,
+   Cold,
+   Radiation,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Army {
+   Immune,
+   Infection,
+}
+
+// how many cells a group that hasn't lost any units fills in our chart
+const CHART_WIDTH: usize = 100;
+
+const ATTACKS: [Attack; 5] = [
+   Attack::Fire,
+   Attack::Slashing,
+   Attack::Bludgeoning,
+   Attack::Cold,
+   Attack::Radiation,
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Group {
+   army: Army,
+   group_id: usize,
+   units: isize,
+   hit_points: isize,
+   weaknesses: Vec<Attack>,
+   immunities: Vec<Attack>,
+   attack_damage: isize,
+   attack_type: Attack,
+   initiative: isize,
+   target: Option<usize>,
+}
+
+/// How a battle ended, with the groups that are left
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+   ImmuneWins(Vec<Group>),
+   InfectionWins(Vec<Group>),
+   /// nobody can kill anyone anymore, so both sides are still here
+   Stalemate(Vec<Group>),
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+   type Input = Vec<Group>;
+   type A = isize;
+   type B = isize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Vec<Group>, ParseError> {
+      let re: Regex = Regex::new(r"(\d+) units each with (\d+) hit points (?:\((.+)\) )?with an attack that does (\d+) (\w+) damage at initiative (\d+)").unwrap();
+      let attributes_re: Regex = Regex::new(r"(\w+) to (\w+)(?:, (\w+))*").unwrap();
+
+      let first_newline = contents.find('\n')
+         .ok_or_else(|| ParseError::new(1, 1, "expected our first army's name on a line of its own"))?;
+
+      let dropped_contents = &contents[first_newline..];
+
+      let raw_armies: Vec<&str> = dropped_contents
+         .split("Infection:")
+         .map(|s| s.trim())
+         .filter(|s| !s.is_empty())
+         .collect();
+
+      let mut army = Army::Infection;
+      let armies: Vec<Vec<Group>> = raw_armies.iter()
+         .map(|raw_army| {
+            let mut group_id = 0;
+            army = if army == Army::Infection { Army::Immune } else { Army::Infection };
+
+            raw_army.lines()
+               .map(|row| {
+                  group_id += 1;
+
+                  let captures = re.captures(row)
+                     .ok_or_else(|| ParseError::at(contents, row, "expected a group like 17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2"))?;
+
+                  let unit_count = parse_at(contents, &captures[1])?;
+                  let hit_points = parse_at(contents, &captures[2])?;
+                  let attack_damage = parse_at(contents, &captures[4])?;
+                  let attack_type = Attack::find(&captures[5])
+                     .ok_or_else(|| ParseError::at(contents, &captures[5], format!("unknown attack type {:?}", &captures[5])))?;
+                  let initiative = parse_at(contents, &captures[6])?;
+
+                  let mut weaknesses = vec![];
+                  let mut immunities = vec![];
+
+                  match captures.get(3) {
+                     Some(raw_attributes) => {
+                        let split_attributes = raw_attributes.as_str().split(';')
+                           .map(|s| s.trim());
+
+                        for split_attribute in split_attributes {
+                           let ar_captures = attributes_re.captures(split_attribute)
+                              .ok_or_else(|| ParseError::at(contents, split_attribute, "expected weaknesses or immunities like weak to fire, cold"))?;
+
+                           let ar_attacks = ar_captures.iter().skip(2)
+                              .filter_map(|maybe_m| {
+                                 maybe_m.and_then(|m| Attack::find(m.as_str()))
+                              })
+                              .collect();
+
+                           if ar_captures[1] == *"weak" {
+                              weaknesses = ar_attacks;
+                           } else {
+                              immunities = ar_attacks;
+                           }
+                        }
+                     }
+                     _ => {}
+                  }
+
+                  Ok(Group {
+                     army,
+                     group_id,
+                     units: unit_count,
+                     hit_points,
+                     weaknesses,
+                     immunities,
+                     attack_damage,
+                     attack_type,
+                     initiative,
+                     target: None,
+                  })
+               })
+               .collect::<Result<Vec<Group>, ParseError>>()
+         })
+         .collect::<Result<_, _>>()?;
+
+      Ok(armies.into_iter().flatten().collect())
+   }
+
+   fn part_a(&self, groups: &Vec<Group>) -> isize {
+      a(groups)
+   }
+
+   fn part_b(&self, groups: &Vec<Group>) -> isize {
+      b(groups)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![Example::sample().a(5216).b(51)]
+   }
+
+   // part a's battle, a bar of how many units each group has left a round
+   fn animate(&self, groups: &Vec<Group>, recorder: &mut Recorder) -> bool {
+      let mut chart = Grid::new(None, CHART_WIDTH, groups.len());
+      run(groups, 0, None, &mut |remaining| {
+         chart = army_chart(groups, remaining);
+         recorder.tick(4, &chart, &army_color);
+      });
+      recorder.finish(4, &chart, &army_color);
+
+      true
+   }
+}
+
+fn a(initial_groups: &Vec<Group>) -> isize {
+   run(initial_groups, 0, None, &mut |_| {}).remaining_units()
+}
+
+fn b(initial_groups: &Vec<Group>) -> isize {
+   // a boost anywhere near isize::MAX overflows our effective power
+   let threshold = min_winning(1..100_000, |boost| {
+      match run(initial_groups, boost, None, &mut |_| {}) {
+         outcome @ Outcome::ImmuneWins(_) => Some(outcome.remaining_units()),
+         _ => None,
+      }
+   });
+
+   threshold.expect("the immune system wins with a big enough boost").outcome
+}
+
+/// Our battle along with everything that happened in it, like the puzzle's worked example
+pub fn trace(initial_groups: &[Group], boost: isize) -> (Outcome, String) {
+   let mut trace = String::new();
+   let outcome = run(initial_groups, boost, Some(&mut trace), &mut |_| {});
+
+   (outcome, trace)
+}
+
+// `on_round` sees the groups still fighting at the start of every round, and once more when the battle is over
+fn run<F>(initial_groups: &[Group], boost: isize, mut trace: Option<&mut String>, on_round: &mut F) -> Outcome where
+   F: FnMut(&[RefCell<Group>]) {
+   let mut groups: Vec<RefCell<Group>> = initial_groups.iter()
+      .map(|g| {
+         let mut group = g.clone();
+
+         if group.army == Army::Immune {
+            group.attack_damage += boost;
+         }
+
+         RefCell::new(group)
+      })
+      .collect();
+
+   let mut found_immune = true;
+   let mut found_infection = true;
+   while found_immune && found_infection {
+      on_round(&groups);
+      if let Some(trace) = trace.as_mut() {
+         trace_armies(trace, &groups);
+         trace.push('\n');
+      }
+
+      // targeting
+      groups.sort_unstable_by(|left, right| {
+         left.borrow().target_order(&right.borrow())
+      });
+
+      let mut taken: Vec<usize> = vec![];
+      let mut potential_damage = vec![];
+      for i in 0..groups.len() {
+         if trace.is_some() {
+            potential_damage.extend(groups[i].borrow().potential_damage(&groups, i, &taken));
+         }
+
+         match groups[i].borrow_mut().set_target(&groups, i, &taken) {
+            Some(found) => taken.push(found),
+            None => {}
+         }
+      }
+
+      if let Some(trace) = trace.as_mut() {
+         trace_potential_damage(trace, potential_damage);
+      }
+
+      // attacking
+      groups.sort_unstable_by(|left, right| {
+         left.borrow().attack_order(&right.borrow())
+      });
+
+      let mut killed_units = 0;
+      for i in 0..groups.len() {
+         let group = groups[i].borrow();
+
+         match group.find_target_index(&groups) {
+            Some(target_index) => {
+               let mut target = groups[target_index].borrow_mut();
+
+               let damage = group.damage_to(&target);
+               let lost_units = target.units_lost(damage);
+
+               killed_units += lost_units;
+
+               // a group that was killed earlier this round doesn't get to attack
+               if let (Some(trace), true) = (trace.as_mut(), group.units > 0) {
+                  trace.push_str(&format!("{} group {} attacks defending group {}, killing {} units\n",
+                                          group.army.name(), group.group_id, target.group_id, lost_units));
+               }
+
+               target.units -= lost_units;
+            }
+            None => {}
+         }
+      }
+
+      if let Some(trace) = trace.as_mut() {
+         trace.push('\n');
+      }
+
+      // check for a stalemate
+      if killed_units == 0 {
+         on_round(&groups);
+         if let Some(trace) = trace.as_mut() {
+            trace_armies(trace, &groups);
+         }
+
+         return Outcome::Stalemate(remaining(&groups));
+      }
+
+      // remove dead groups
+      groups = groups.iter()
+         .filter(|g| g.borrow().units > 0)
+         .map(|g| g.clone())
+         .collect();
+
+      // check for exit
+      found_immune = false;
+      found_infection = false;
+      for group in groups.iter() {
+         match group.borrow().army {
+            Army::Infection => found_infection = true,
+            Army::Immune => found_immune = true,
+         }
+      }
+   }
+
+   on_round(&groups);
+   if let Some(trace) = trace.as_mut() {
+      trace_armies(trace, &groups);
+   }
+
+   if found_immune {
+      Outcome::ImmuneWins(remaining(&groups))
+   } else {
+      Outcome::InfectionWins(remaining(&groups))
+   }
+}
+
+fn remaining(groups: &[RefCell<Group>]) -> Vec<Group> {
+   groups.iter()
+      .map(|group| group.borrow().clone())
+      .filter(|group| group.units > 0)
+      .collect()
+}
+
+// how many units each army's groups have, like
+//
+// Immune System:
+// Group 1 contains 17 units
+fn trace_armies(trace: &mut String, groups: &[RefCell<Group>]) {
+   let mut groups: Vec<Group> = remaining(groups);
+   groups.sort_by_key(|group| group.group_id);
+
+   for army in [Army::Immune, Army::Infection].iter() {
+      trace.push_str(&format!("{}:\n", army.name()));
+
+      let mut found = false;
+      for group in groups.iter().filter(|group| group.army == *army) {
+         trace.push_str(&format!("Group {} contains {} units\n", group.group_id, group.units));
+         found = true;
+      }
+
+      if !found {
+         trace.push_str("No groups remain.\n");
+      }
+   }
+}
+
+// every target each group could have picked, the infection first and then in group order
+fn trace_potential_damage(trace: &mut String, mut potential_damage: Vec<(Army, usize, usize, isize)>) {
+   potential_damage.sort_by_key(|&(army, attacker, defender, _)| (army == Army::Immune, attacker, defender));
+
+   for (army, attacker, defender, damage) in potential_damage {
+      trace.push_str(&format!("{} group {} would deal defending group {} {} damage\n", army.name(), attacker, defender, damage));
+   }
+   trace.push('\n');
+}
+
+// a row for each of our initial groups, as full as the share of its units it has left
+fn army_chart(initial_groups: &[Group], groups: &[RefCell<Group>]) -> Grid<Option<Army>> {
+   let mut chart = Grid::new(None, CHART_WIDTH, initial_groups.len());
+
+   for (y, initial) in initial_groups.iter().enumerate() {
+      let units = groups.iter()
+         .map(|group| group.borrow())
+         .find(|group| group.army == initial.army && group.group_id == initial.group_id)
+         .map(|group| group.units)
+         .unwrap_or(0);
+
+      // round up so a group with any units left still shows
+      let width = (units * CHART_WIDTH as isize + initial.units - 1) / initial.units;
+      for x in 0..width {
+         chart.set(x, y as isize, Some(initial.army));
+      }
+   }
+
+   chart
+}
+
+fn army_color(army: &Option<Army>) -> Option<u32> {
+   Some(match army {
+      Some(Army::Immune) => 0x5DADE2FF,
+      Some(Army::Infection) => 0xC0392BFF,
+      None => 0x000000FF,
+   })
+}
+
+impl Group {
+   fn effective_power(&self) -> isize {
+      self.units * self.attack_damage
+   }
+
+   fn damage_to(&self, other: &Group) -> isize {
+      if other.immunities.contains(&self.attack_type) {
+         0
+      } else if other.weaknesses.contains(&self.attack_type) {
+         self.effective_power() * 2
+      } else {
+         self.effective_power()
+      }
+   }
+
+   fn units_lost(&self, damage: isize) -> isize {
+      (damage / self.hit_points).min(self.units)
+   }
+
+   fn find_target_index(&self, groups: &Vec<RefCell<Group>>) -> Option<usize> {
+      self.target.map(|target| {
+         for i in 0..groups.len() {
+            let group = &groups[i].borrow();
+
+            // return our target if we found it
+            if self.army != group.army && group.group_id == target {
+               return i;
+            }
+         }
+
+         panic!("There should always be a match");
+      })
+   }
+
+   // who we could pick from the groups left, and how much damage we'd do to them
+   fn potential_damage(&self, groups: &[RefCell<Group>], self_i: usize, taken: &[usize]) -> Vec<(Army, usize, usize, isize)> {
+      (0..groups.len())
+         .filter(|&i| i != self_i && !taken.contains(&i))
+         .map(|i| groups[i].borrow())
+         .filter(|group| group.army != self.army)
+         .map(|group| (self.army, self.group_id, group.group_id, self.damage_to(&group)))
+         .filter(|&(_, _, _, damage)| damage > 0)
+         .collect()
+   }
+
+   fn set_target(&mut self, groups: &Vec<RefCell<Group>>, self_i: usize, taken: &Vec<usize>) -> Option<usize> {
+      let mut found_i = None;
+      let mut max_damage = isize::min_value();
+
+      for i in 0..groups.len() {
+         // don't borrow our mutable borrow
+         if i != self_i {
+            let group = &groups[i].borrow();
+
+            // don't attack ourselves or something that isn't available
+            if self.army != group.army && !taken.contains(&i) {
+               let damage = self.damage_to(&group);
+               if damage <= 0 {
+                  // we can't deal any damage so don't do anything
+                  continue;
+               } else if damage > max_damage {
+                  max_damage = damage;
+                  found_i = Some(i);
+               } else if damage == max_damage {
+                  let last_group = &groups[found_i.unwrap()].borrow();
+
+                  match group.effective_initiative_order(last_group) {
+                     Ordering::Greater => {
+                        // only do something if this new group is greater
+                        found_i = Some(i)
+                     }
+                     _ => {}
+                  }
+               }
+            }
+         }
+      }
+
+      self.target = found_i.map(|i| groups[i].borrow().group_id);
+
+      found_i
+   }
+
+   fn target_order(&self, other: &Group) -> Ordering {
+      self.effective_initiative_order(other).reverse()
+   }
+
+   fn effective_initiative_order(&self, other: &Group) -> Ordering {
+//      println!("{} {} {} {} {:?} {:?} {:?}", self.effective_power(), other.effective_power(), self.initiative, other.initiative,
+//               self.effective_power().cmp(&other.effective_power()),
+//               self.initiative.cmp(&other.initiative),
+//               self.effective_power().cmp(&other.effective_power())
+//                  .then(self.initiative.cmp(&other.initiative))
+//                  );
+
+      self.effective_power().cmp(&other.effective_power())
+         .then(self.initiative.cmp(&other.initiative))
+      //.reverse()
+   }
+
+   fn attack_order(&self, other: &Group) -> Ordering {
+      self.initiative.cmp(&other.initiative).reverse()
+   }
+
+   //fn id_order(&self, other: &Group) -> Ordering {
+   //   self.group_id.cmp(&other.group_id)
+   //}
+}
+
+impl fmt::Display for Group {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{}[{}]", self.army, self.group_id)
+   }
+}
+
+impl Outcome {
+   pub fn groups(&self) -> &[Group] {
+      match self {
+         Outcome::ImmuneWins(groups) | Outcome::InfectionWins(groups) | Outcome::Stalemate(groups) => groups,
+      }
+   }
+
+   pub fn remaining_units(&self) -> isize {
+      self.groups().iter().map(|group| group.units).sum()
+   }
+}
+
+impl Army {
+   // what the puzzle calls us
+   fn name(&self) -> &'static str {
+      match self {
+         Army::Immune => "Immune System",
+         Army::Infection => "Infection",
+      }
+   }
+}
+
+impl Attack {
+   fn find(raw_attack: &str) -> Option<Attack> {
+      for attack in ATTACKS.iter() {
+         if attack.to_string() == raw_attack {
+            return Some(attack.clone());
+         }
+      }
+
+      return None;
+   }
+}
+
+impl fmt::Display for Attack {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{}", format!("{:?}", self).to_lowercase())
+   }
+}
+
+impl fmt::Display for Army {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{}", format!("{:?}", self))
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   // the puzzle's worked example, round by round
+   #[test]
+   fn sample_trace() {
+      let sample = Day24.parse(include_str!("../sample_input.txt"), true).unwrap();
+
+      assert_eq!(include_str!("../sample_trace.txt"), trace(&sample, 0).1);
+   }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use regex::Regex;
+use std::cell::RefCell;
+
+use common::*;
+use common::geometry::Point;
+
+pub type Coordinate = Point<4>;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+   type Input = Vec<Coordinate>;
+   type A = usize;
+   type B = &'static str;
+
+   // there's no part b on the last day
+   const PARTS: &'static [Part] = &[Part::A];
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Vec<Coordinate>, ParseError> {
+      let re: Regex = Regex::new(r"(-?\d+),(-?\d+),(-?\d+),(-?\d+)").unwrap();
+
+      let cords: Vec<Coordinate> = contents.lines()
+         .map(|row| {
+            let captures = re.captures(row)
+               .ok_or_else(|| ParseError::at(contents, row, "expected a point like 0,-3,2,1"))?;
+            let iter = captures.iter().skip(1);
+
+            let input: Vec<isize> = iter
+               .map(|capture| parse_at(contents, capture.unwrap().as_str()))
+               .collect::<Result<_, _>>()?;
+
+            Ok(Point::new([input[0], input[1], input[2], input[3]]))
+         })
+         .collect::<Result<_, _>>()?;
+
+      Ok(cords)
+   }
+
+   fn part_a(&self, coordinates: &Vec<Coordinate>) -> usize {
+      a(coordinates).len()
+   }
+
+   fn part_b(&self, _: &Vec<Coordinate>) -> &'static str {
+      unreachable!("there's no part b on the last day")
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      Example::numbered("test_input_{}.txt", Part::A, vec![2, 4, 3, 8])
+   }
+}
+
+fn a(coordinates: &Vec<Coordinate>) -> Vec<Vec<Coordinate>> {
+   let mut constellations: Vec<RefCell<Vec<Coordinate>>> = Vec::new();
+
+   for coordinate in coordinates {
+      let mut found = vec![];
+
+      for i in 0..constellations.len() {
+         for other_coord in constellations[i].borrow().iter() {
+            if coordinate.distance(other_coord) <= 3 {
+               found.push(i);
+
+               // one match is good enough (and we don't want to double count in found)
+               break;
+            }
+         }
+      }
+
+      if found.len() > 0 {
+         {
+            let main = *found.first().unwrap();
+            let mut closest_constellation = constellations[main].borrow_mut();
+            closest_constellation.push(coordinate.clone());
+
+            // merge our constellations
+            for i in 1..found.len() {
+               closest_constellation.extend(constellations[found[i]].borrow().iter());
+            }
+         }
+
+         // drop the merged constellations
+         for i in (1..found.len()).rev() {
+            constellations.remove(found[i]);
+         }
+      } else {
+         let new_constellation = RefCell::new(vec![coordinate.clone()]);
+
+         constellations.push(new_constellation);
+      }
+   }
+
+   constellations.iter()
+      .map(|cell| cell.borrow().clone())
+      .collect()
+}
\ No newline at end of file
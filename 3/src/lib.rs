@@ -0,0 +1,126 @@
+use common::*;
+
+pub struct Claim {
+   id: String,
+   x: usize,
+   y: usize,
+   width: usize,
+   height: usize,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+   type Input = (Vec<Claim>, Vec<Vec<i32>>);
+   type A = i32;
+   type B = String;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(Vec<Claim>, Vec<Vec<i32>>), ParseError> {
+      let mut max_width: usize = 0;
+      let mut max_height: usize = 0;
+
+      let claims: Vec<Claim> = contents.lines()
+         .map(|row| {
+            let bad_claim = || ParseError::at(contents, row, "expected a claim like #1 @ 1,3: 4x4");
+            let mut split_row = row.split_whitespace();
+
+            let id = split_row.next()
+               .and_then(|id| id.strip_prefix('#'))
+               .ok_or_else(bad_claim)?
+               .to_string();
+            split_row.next();
+            let x_y: Vec<&str> = split_row.next().ok_or_else(bad_claim)?
+               .splitn(3, |c| c == ',' || c == ':')
+               .collect();
+            let w_h: Vec<&str> = split_row.next().ok_or_else(bad_claim)?
+               .splitn(3, 'x')
+               .collect();
+            if x_y.len() < 2 || w_h.len() < 2 {
+               return Err(bad_claim());
+            }
+
+            let x = parse_at(contents, x_y[0])?;
+            let y = parse_at(contents, x_y[1])?;
+            let width = parse_at(contents, w_h[0])?;
+            let height = parse_at(contents, w_h[1])?;
+
+            if x + width > max_width {
+               max_width = x + width;
+            }
+            if y + height > max_height {
+               max_height = y + height;
+            }
+
+            Ok(Claim {
+               id,
+               x,
+               y,
+               width,
+               height,
+            })
+         })
+         .collect::<Result<_, _>>()?;
+
+      max_width += 1;
+      max_height += 1;
+
+      let mut cloth = vec![vec![0; max_height]; max_width];
+      for claim in &claims {
+         fill_cloth(claim, &mut cloth);
+      }
+
+      Ok((claims, cloth))
+   }
+
+   fn part_a(&self, (_, cloth): &(Vec<Claim>, Vec<Vec<i32>>)) -> i32 {
+      a(cloth)
+   }
+
+   fn part_b(&self, (claims, cloth): &(Vec<Claim>, Vec<Vec<i32>>)) -> String {
+      b(claims, cloth)
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      vec![]
+   }
+}
+
+fn a(cloth: &Vec<Vec<i32>>) -> i32 {
+   let mut overlaps = 0;
+   for column in cloth {
+      for cell in column {
+         if *cell > 1 {
+            overlaps += 1;
+         }
+      }
+   }
+
+   overlaps
+}
+
+fn b(claims: &Vec<Claim>, cloth: &Vec<Vec<i32>>) -> String {
+   for claim in claims {
+      let mut overlap = 0;
+      for y in claim.y..(claim.y + claim.height) {
+         for x in claim.x..(claim.x + claim.width) {
+            if cloth[x][y] > 1 {
+               overlap += 1;
+            }
+         }
+      }
+
+      if overlap == 0 {
+         return claim.id.clone();
+      }
+   }
+
+   panic!("every claim overlaps another")
+}
+
+fn fill_cloth(claim: &Claim, cloth: &mut Vec<Vec<i32>>) {
+   for y in claim.y..(claim.y + claim.height) {
+      for x in claim.x..(claim.x + claim.width) {
+         cloth[x][y] += 1;
+      }
+   }
+}
\ No newline at end of file
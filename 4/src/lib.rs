@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use chrono::prelude::*;
+use regex::Regex;
+
+use common::*;
+
+enum Event {
+   Shift(usize),
+   Sleep,
+   Wake,
+}
+
+struct Row(DateTime<Utc>, Event);
+
+pub struct Day4;
+
+impl Solution for Day4 {
+   type Input = HashMap<usize, [usize; 60]>;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<HashMap<usize, [usize; 60]>, ParseError> {
+      let re = Regex::new(r"\[(.*)\] (.+)").unwrap();
+      let shift_re = Regex::new(r"#(\d+)").unwrap();
+
+      let mut rows: Vec<Row> = contents.lines()
+         .map(|row| {
+            let parsed_row = re.captures(row)
+               .ok_or_else(|| ParseError::at(contents, row, "expected a row like [1518-11-01 00:00] falls asleep"))?;
+
+            let time = parsed_row.get(1).unwrap().as_str();
+            let date_time = Utc.datetime_from_str(time, "%Y-%m-%d %H:%M")
+               .map_err(|e| ParseError::at(contents, time, format!("can't parse {:?}: {}", time, e)))?;
+
+            let event = match &parsed_row[2] {
+               "wakes up" => Event::Wake,
+               "falls asleep" => Event::Sleep,
+               shift_row => {
+                  let shift_change = shift_re.captures(shift_row)
+                     .ok_or_else(|| ParseError::at(contents, shift_row, "expected a guard's #id"))?;
+
+                  Event::Shift(parse_at(contents, shift_change.get(1).unwrap().as_str())?)
+               }
+            };
+
+            Ok(Row(date_time, event))
+         })
+         .collect::<Result<_, _>>()?;
+
+      rows.sort_by_key(|row| row.0);
+
+      // debug
+      // for row in &rows {
+      //    println!("{} {}", row.0, row.1);
+      // }
+
+      Ok(calculate_guard_schedules(&rows))
+   }
+
+   fn part_a(&self, guard_schedules: &HashMap<usize, [usize; 60]>) -> usize {
+      a(guard_schedules)
+   }
+
+   fn part_b(&self, guard_schedules: &HashMap<usize, [usize; 60]>) -> usize {
+      b(guard_schedules)
+   }
+}
+
+fn calculate_guard_schedules(rows: &Vec<Row>) -> HashMap<usize, [usize; 60]> {
+   let mut guard_schedules = HashMap::new();
+
+   let mut current_guard = 0;
+   let mut guard_sleep_count = [0; 60];
+   let mut awake = true;
+   for row in rows {
+      let minute = row.0.minute() as usize;
+
+      match row.1 {
+         Event::Wake => {
+            if !awake {
+               awake = true;
+               for i in minute..60 {
+                  guard_sleep_count[i] -= 1;
+               }
+            }
+         }
+         Event::Sleep => {
+            if awake {
+               awake = false;
+               for i in minute..60 {
+                  guard_sleep_count[i] += 1;
+               }
+            }
+         }
+         Event::Shift(guard) => {
+            // write out our last guard
+            if current_guard != 0 {
+               guard_schedules.insert(current_guard, guard_sleep_count);
+
+               // debug
+               // print!("{:4}: ", current_guard);
+               // for count in guard_sleep_count.iter() {
+               //    print!("{:2}", count);
+               // }
+               // println!("");
+            }
+
+            current_guard = guard;
+            guard_sleep_count = match guard_schedules.get(&current_guard) {
+               Some(last_sleep) => *last_sleep,
+               _ => [0; 60],
+            };
+            awake = true;
+         }
+      }
+   }
+
+   // write out our last guard
+   guard_schedules.insert(current_guard, guard_sleep_count);
+
+   // debug
+   // print!("{:4}: ", current_guard);
+   // for count in guard_sleep_count.iter() {
+   //    print!("{:2}", count);
+   // }
+   // println!("");
+   // println!("Finished Counting");
+
+   return guard_schedules;
+}
+
+fn a(guard_schedules: &HashMap<usize, [usize; 60]>) -> usize {
+   let mut max_sleep = 0;
+   let mut max_guard = 0;
+   for (id, &guard_schedule) in guard_schedules.iter() {
+      let mut sleep = 0;
+
+      for count in guard_schedule.iter() {
+         sleep += *count;
+      }
+
+      // debug
+      // print!("{:4}: {:2} :", id, sleep);
+      // for count in guard_schedule.iter() {
+      //    print!("{:2}", count);
+      // }
+      // println!("");
+
+      if max_sleep < sleep {
+         //println!("New Max: {}", sleep);
+         max_sleep = sleep;
+         max_guard = *id;
+      }
+   }
+
+   let mut max_minute = 0;
+   let mut max_minute_count = 0;
+   for (minute, count) in guard_schedules.get(&max_guard).unwrap().iter().enumerate() {
+      if max_minute_count < *count {
+         max_minute_count = *count;
+         max_minute = minute;
+      }
+   }
+
+   max_guard * max_minute
+}
+
+fn b(guard_schedules: &HashMap<usize, [usize; 60]>) -> usize {
+   let mut max_sleep_count = 0;
+   let mut max_guard = 0;
+   let mut max_minute = 0;
+   for (id, &guard_schedule) in guard_schedules.iter() {
+      for (minute, count) in guard_schedule.iter().enumerate() {
+         if max_sleep_count < *count {
+            max_sleep_count = *count;
+            max_guard = *id;
+            max_minute = minute;
+         }
+      }
+   }
+
+   max_guard * max_minute
+}
\ No newline at end of file
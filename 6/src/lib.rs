@@ -0,0 +1,223 @@
+use regex::Regex;
+
+use common::*;
+use common::coordinates::Cells;
+use common::coordinates::Grid;
+use common::coordinates::Loci;
+
+pub struct Coordinates {
+   locations: Vec<Loci>,
+   min: Loci,
+   max: Loci,
+   region_range: usize,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+   type Input = Coordinates;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, is_sample: bool) -> Result<Coordinates, ParseError> {
+      let re: Regex = Regex::new(r"(\d+), (\d+)").unwrap();
+
+      let mut min = Loci::max_value();
+      let mut max = Loci::new(0, 0);
+      let locations: Vec<Loci> = contents.lines()
+         .map(|row| {
+            let parsed_row = re.captures(row)
+               .ok_or_else(|| ParseError::at(contents, row, "expected a coordinate like 1, 6"))?;
+
+            let x = parse_at(contents, &parsed_row[1])?;
+            let y = parse_at(contents, &parsed_row[2])?;
+
+            if x < min.x() {
+               min = min.with_x(x);
+            }
+            if x > max.x() {
+               max = max.with_x(x);
+            }
+
+            if y < min.y() {
+               min = min.with_y(y);
+            }
+            if y > max.y() {
+               max = max.with_y(y);
+            }
+
+            Ok(Loci::new(x, y))
+         })
+         .collect::<Result<_, _>>()?;
+
+      // give us some breathing room
+      min = min.sub(1, 1);
+      max = max.add(1, 1);
+
+      // get the region depending on if we're running the sample or not
+      let region_range;
+      if is_sample {
+         region_range = 32;
+      } else {
+         region_range = 10000;
+      }
+
+      Ok(Coordinates {
+         locations,
+         min,
+         max,
+         region_range,
+      })
+   }
+
+   fn part_a(&self, coordinates: &Coordinates) -> usize {
+      a(&coordinates.locations, &coordinates.min, &coordinates.max)
+   }
+
+   fn part_b(&self, coordinates: &Coordinates) -> usize {
+      b(&coordinates.locations, &coordinates.min, &coordinates.max, coordinates.region_range)
+   }
+}
+
+fn a(locations: &Vec<Loci>, min: &Loci, max: &Loci) -> usize {
+   let mut manhattan_grid: coordinates::Grid<Option<&Loci>> = Grid::new_loci_offset(
+      None,
+      &max.sub_loci(min),
+      min,
+   );
+
+   // for each coordinate in the graph find the closest "location"
+   for loci in manhattan_grid.locis() {
+      manhattan_grid.set_loci(&loci, find_closest_location(&loci, locations))
+   }
+
+   // trim infinity locations
+   let mut finite_locations = locations.iter()
+      .map(|location| -> &Loci { &location })
+      .collect();
+
+   prune_edges(&manhattan_grid, &mut finite_locations);
+
+   // debug
+   //for loci in manhattan_grid.locis() {
+   //   match manhattan_grid.get(&loci) {
+   //      Some(_) => print!("#"),
+   //      _ => print!("."),
+   //   }
+   //
+   //   if loci.y() == manhattan_grid.y_min() {
+   //      println!();
+   //   }
+   //}
+
+   //for finite_location in finite_locations.iter() {
+   //    println!("{:?}", finite_location);
+   //}
+
+   let mut max_area = 0;
+   for location in finite_locations {
+      let mut area = 0;
+
+      for maybe_loci in manhattan_grid.iter() {
+         match maybe_loci {
+            Some(loci) => if *loci == location {
+               area += 1;
+            }
+            _ => {}
+         }
+      }
+
+      if area > max_area {
+         max_area = area;
+      }
+   }
+
+   max_area
+}
+
+// anything closest to our edge keeps going forever
+fn prune_edges<'a, G: Cells<Option<&'a Loci>>>(grid: &G, finite_locations: &mut Vec<&'a Loci>) {
+   for x in grid.x_range() {
+      prune_infinite(*grid.get(x, grid.y_min()), finite_locations);
+      prune_infinite(*grid.get(x, grid.y_max() - 1), finite_locations);
+   }
+   for y in grid.y_range() {
+      prune_infinite(*grid.get(grid.x_min(), y), finite_locations);
+      prune_infinite(*grid.get(grid.x_max() - 1, y), finite_locations);
+   }
+}
+
+fn prune_infinite(infinite_location: Option<&Loci>, finite_locations: &mut Vec<&Loci>) {
+   match infinite_location {
+      Some(infinite) => {
+         finite_locations.iter()
+            .position(|loci| *loci == infinite)
+            .map(|position| finite_locations.remove(position));
+      }
+      _ => {}
+   }
+}
+
+
+fn b(locations: &Vec<Loci>, min: &Loci, max: &Loci, region_range: usize) -> usize {
+   let mut region_grid: coordinates::Grid<usize> = Grid::new_loci_offset(
+      0,
+      &max.sub_loci(min),
+      min,
+   );
+
+   'main: for loci in region_grid.locis() {
+      let mut total_distance = 0;
+      for location in locations {
+         total_distance += location.distance(&loci);
+         if total_distance > region_range {
+            // we hit our limit so break out
+            continue 'main;
+         }
+      }
+
+      // if we get here we know we're in a region
+      region_grid.set_loci(&loci, 1);
+   }
+
+   //for y in 0..height {
+   //    for x in 0..width {
+   //        match grid[x][y] {
+   //            true => print!("#"),
+   //            _ => print!("."),
+   //        }
+   //    }
+   //    println!()
+   //}
+
+   // count up the size of our region
+   region_grid.iter()
+      .fold(0, |sum, cell| -> usize {
+         sum + cell
+      })
+}
+
+fn find_closest_location<'a>(current_loci: &Loci, locations: &'a Vec<Loci>) -> Option<&'a Loci> {
+   let mut min_distance = usize::max_value();
+   let mut closest = None;
+   let mut dup = false;
+   for location in locations {
+      let distance = current_loci.distance(location);
+      //println!("{}", distance);
+
+      if distance < min_distance {
+         closest = Some(location);
+         min_distance = distance;
+         dup = false;
+      } else if distance == min_distance {
+         dup = true;
+      }
+   }
+
+   // we found a duplicate so don't return anything
+   if dup {
+      return None;
+   }
+
+   return closest;
+}
\ No newline at end of file
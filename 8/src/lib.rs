@@ -0,0 +1,114 @@
+use common::*;
+
+pub struct Node {
+   children: Vec<Node>,
+   metadata: Vec<usize>,
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+   type Input = Node;
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<Node, ParseError> {
+      let numbers: Vec<usize> = contents.split_whitespace()
+         .map(|raw_num| parse_at(contents, raw_num))
+         .collect::<Result<_, _>>()?;
+
+      let (root, _) = parse_node(&numbers, 0)
+         .ok_or_else(|| {
+            let end = &contents[contents.trim_end().len()..];
+
+            ParseError::at(contents, end, "the tree ends before all of its children and metadata do")
+         })?;
+
+      Ok(root)
+   }
+
+   fn part_a(&self, root: &Node) -> usize {
+      a(root)
+   }
+
+   fn part_b(&self, root: &Node) -> usize {
+      b(root)
+   }
+}
+
+// nothing when we run out of numbers
+fn parse_node(data: &Vec<usize>, start: usize) -> Option<(Node, usize)> {
+   let mut offset = start;
+   let children_count = *data.get(offset)?;
+   offset += 1;
+   let metadata_count = *data.get(offset)?;
+   offset += 1;
+
+   let mut children: Vec<Node> = vec![];
+   for _ in 0..children_count {
+      let (child, end_offset) = parse_node(data, offset)?;
+
+      children.push(child);
+      offset = end_offset;
+   }
+
+   let mut metadata: Vec<usize> = vec![];
+   for _ in 0..metadata_count {
+      metadata.push(*data.get(offset)?);
+      offset += 1;
+   }
+
+   Some((Node {
+      children,
+      metadata,
+   }, offset))
+}
+
+fn a(root: &Node) -> usize {
+   fn sum_metadata(node: &Node) -> usize {
+      let children_sum = node.children.iter()
+         .fold(0, |sum, child| {
+            sum + sum_metadata(child)
+         });
+
+
+      let metadata_sum = node.metadata.iter()
+         .fold(0, |total, current| {
+            total + current
+         });
+
+      children_sum + metadata_sum
+   }
+
+   sum_metadata(root)
+}
+
+fn b(root: &Node) -> usize {
+   fn sum_data(node: &Node) -> usize {
+      if node.children.is_empty() {
+         // no children so just sum our metadata
+         return node.metadata.iter()
+            .fold(0, |total, current| {
+               total + current
+            });
+      }
+      else {
+         // we have children, so use the metadata to get our children values
+         return node.metadata.iter()
+            .fold(0, |total, metadata| {
+               let mut child_sum = 0;
+               if *metadata > 0 {
+                  let child_index = metadata - 1;
+
+                  child_sum = node.children.get(child_index)
+                     .map(sum_data)
+                     .unwrap_or(0);
+               }
+
+               total + child_sum
+            });
+      }
+   }
+
+   sum_data(root)
+}
\ No newline at end of file
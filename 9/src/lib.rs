@@ -0,0 +1,92 @@
+use doubly::DoublyLinkedList;
+
+use regex::Regex;
+
+use common::*;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+   // players and the last marble
+   type Input = (usize, usize);
+   type A = usize;
+   type B = usize;
+
+   fn parse(&self, contents: &str, _is_sample: bool) -> Result<(usize, usize), ParseError> {
+      let re: Regex = Regex::new(r"(\d+) players; last marble is worth (\d+) points").unwrap();
+
+      let parsed = re.captures(contents)
+         .ok_or_else(|| ParseError::at(contents, contents, "expected a game like 10 players; last marble is worth 1618 points"))?;
+
+      let players = parse_at(contents, &parsed[1])?;
+      let max_marble = parse_at(contents, &parsed[2])?;
+
+      Ok((players, max_marble))
+   }
+
+   fn part_a(&self, &(players, max_marble): &(usize, usize)) -> usize {
+      a(players, max_marble)
+   }
+
+   fn part_b(&self, &(players, max_marble): &(usize, usize)) -> usize {
+      b(players, max_marble)
+   }
+}
+
+fn a(players: usize, max_marble: usize) -> usize {
+   play_game(players, max_marble)
+}
+
+fn b(players: usize, max_marble: usize) -> usize {
+   play_game(players, max_marble * 100)
+}
+
+fn play_game(players: usize, max_marble: usize) -> usize {
+   let mut scores = vec![0; players + 1];
+   // play the first 2 plays for simplicity
+   let mut current_player = 2;
+   let mut current_index = 1;
+   let mut circle: DoublyLinkedList<usize> = DoublyLinkedList::new();
+   circle.push_back(0);
+   circle.push_back(1);
+
+   for marble in 2..=max_marble {
+      if (marble % 23) == 0 {
+         // special case
+         scores[current_player] += marble;
+
+         if current_index < 7 {
+            current_index = circle.len() - (7 - current_index);
+         } else {
+            current_index -= 7;
+         }
+
+         let removed_marble = circle.remove(current_index);
+
+         scores[current_player] += removed_marble;
+      } else {
+         // normal play
+         current_index += 2;
+         if current_index > circle.len() {
+            // loop around and insert at the beginning
+            current_index = 1;
+         }
+
+         circle.insert(current_index, marble);
+      }
+
+      current_player += 1;
+      if current_player > players {
+         current_player = 1;
+      }
+   }
+
+   return scores.iter()
+      .fold(0, |max, score| {
+         if *score > max {
+            *score
+         } else {
+            max
+         }
+      });
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::env;
+use std::process;
+
+use common::cli;
+use common::cli::{Options, USAGE};
+use common::{run_day, Day, Part, Status};
+
+use crate::registry::DAYS;
+
+mod registry;
+
+// cargo run --bin aoc -- run <day>|--all [--part a|b] [--input <path>|-] [--record] [--bench [--runs <n>] [--warmups <n>] [--baseline]] [--png <path>] [--animate <path> [--every <n>] [--max-mb <n>]]
+fn main() {
+   let args: Vec<String> = env::args().skip(1).collect();
+
+   let (days, options) = match parse_args(&args) {
+      Ok(parsed) => parsed,
+      Err(message) => {
+         eprintln!("{}\nusage: aoc run <day>|--all {}", message, USAGE);
+         process::exit(2);
+      }
+   };
+   cli::set_options(options);
+
+   let mut counts: HashMap<Status, usize> = HashMap::new();
+   let mut failed = vec![];
+   let mut regressed = vec![];
+   for (day, solution) in days {
+      for run in run_day(day, solution) {
+         if let Some(report) = &run.benchmark {
+            for regression in report.regressions.iter() {
+               regressed.push(format!("{} {} {}", run.day, run.name, regression));
+            }
+         }
+
+         for part in [Part::A, Part::B].iter() {
+            if let Some(status) = run.status(*part) {
+               *counts.entry(status).or_insert(0) += 1;
+
+               if status == Status::Fail {
+                  failed.push(format!("{} {} {}", run.day, run.name, part));
+               }
+            }
+         }
+      }
+   }
+
+   println!("Passed: {}, Failed: {}, Unknown: {}",
+            counts.get(&Status::Pass).unwrap_or(&0),
+            counts.get(&Status::Fail).unwrap_or(&0),
+            counts.get(&Status::Unknown).unwrap_or(&0));
+
+   // timings are too noisy to fail on
+   if !regressed.is_empty() {
+      println!("\u{001B}[31mRegressions:\u{001B}[0m\n  {}", regressed.join("\n  "));
+   }
+
+   if !failed.is_empty() {
+      eprintln!("Failed: {}", failed.join(", "));
+      process::exit(1);
+   }
+}
+
+// a day's name along with how to solve it
+type Registered = (&'static str, &'static dyn Day);
+
+fn parse_args(args: &[String]) -> Result<(Vec<Registered>, Options), String> {
+   match args.first().map(|command| command.as_ref()) {
+      Some("run") => {}
+      Some(other) => return Err(format!("unknown command: {}", other)),
+      None => return Err("missing a command".to_string()),
+   }
+
+   let days: Vec<Registered> = match args.get(1).map(|day| day.as_ref()) {
+      Some("--all") => DAYS.to_vec(),
+      Some(day) => DAYS.iter()
+         .filter(|(name, _)| *name == day)
+         .cloned()
+         .collect(),
+      None => return Err("missing a day".to_string()),
+   };
+   if days.is_empty() {
+      return Err(format!("there's no day {}", args[1]));
+   }
+
+   let options = Options::parse(args.iter().skip(2).cloned())?;
+   if days.len() > 1 && options.input.is_some() {
+      return Err("--input only makes sense for a single day".to_string());
+   }
+
+   Ok((days, options))
+}
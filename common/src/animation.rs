@@ -0,0 +1,195 @@
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::canvas::Image;
+use crate::coordinates::Cells;
+use crate::error::Error;
+
+/// Raw frames we keep around before we start throwing half of them away, 64MB
+pub const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Collects a frame every so many ticks of a simulation, to save as an animated GIF or PNG.
+///
+/// Once our frames take up more than `max_bytes` we drop every other one and only keep half as many from then on,
+/// so a long simulation still fits, just more choppily
+#[derive(Clone, Debug)]
+pub struct Recorder {
+   every: usize,
+   max_bytes: usize,
+   // hundredths of a second
+   delay: u16,
+   ticks: usize,
+   frames: Vec<Image>,
+}
+
+impl Default for Recorder {
+   fn default() -> Recorder {
+      Recorder::new()
+   }
+}
+
+impl Recorder {
+   pub fn new() -> Recorder {
+      Recorder {
+         every: 1,
+         max_bytes: DEFAULT_MAX_BYTES,
+         delay: 5,
+         ticks: 0,
+         frames: vec![],
+      }
+   }
+
+   /// Only keep a frame every `every` ticks
+   pub fn every(mut self, every: usize) -> Recorder {
+      self.every = every.max(1);
+      self
+   }
+
+   pub fn max_bytes(mut self, max_bytes: usize) -> Recorder {
+      self.max_bytes = max_bytes;
+      self
+   }
+
+   /// How long each frame shows for, in hundredths of a second
+   pub fn delay(mut self, delay: u16) -> Recorder {
+      self.delay = delay;
+      self
+   }
+
+   fn is_due(&self) -> bool {
+      self.ticks.is_multiple_of(self.every)
+   }
+
+   /// Counts a tick of our simulation, rendering it like `Image::render` if it's one we keep
+   pub fn tick<G, T, C>(&mut self, pixel_size: usize, grid: &G, colorizer: &C) where
+      G: Cells<T>,
+      C: Fn(&T) -> Option<u32> {
+      if self.is_due() {
+         self.push(Image::render(pixel_size, grid, colorizer));
+      }
+
+      self.ticks += 1;
+   }
+
+   /// Renders where our simulation ended up if our last tick was skipped, so we always see how it finishes
+   pub fn finish<G, T, C>(&mut self, pixel_size: usize, grid: &G, colorizer: &C) where
+      G: Cells<T>,
+      C: Fn(&T) -> Option<u32> {
+      if self.ticks > 0 && !(self.ticks - 1).is_multiple_of(self.every) {
+         self.push(Image::render(pixel_size, grid, colorizer));
+      }
+   }
+
+   fn push(&mut self, frame: Image) {
+      self.frames.push(frame);
+
+      while self.frames.len() > 1 && self.bytes() > self.max_bytes {
+         let mut index = 0;
+         self.frames.retain(|_| {
+            index += 1;
+            index % 2 == 1
+         });
+         self.every *= 2;
+      }
+   }
+
+   fn bytes(&self) -> usize {
+      self.frames.iter()
+         .map(|frame| frame.width() * frame.height() * 4)
+         .sum()
+   }
+
+   pub fn frames(&self) -> &[Image] {
+      &self.frames
+   }
+
+   /// How many ticks we've been through, not how many frames we kept
+   pub fn ticks(&self) -> usize {
+      self.ticks
+   }
+
+   pub fn write_gif<W: Write>(&self, out: W) -> io::Result<()> {
+      let (width, height) = self.gif_size()?;
+
+      let mut encoder = gif::Encoder::new(out, width, height, &[])
+         .map_err(io::Error::other)?;
+      encoder.set_repeat(gif::Repeat::Infinite)
+         .map_err(io::Error::other)?;
+
+      for image in self.frames.iter() {
+         let mut rgba: Vec<u8> = image.rgba().flatten().collect();
+
+         let mut frame = gif::Frame::from_rgba_speed(width, height, &mut rgba, 10);
+         frame.delay = self.delay;
+         encoder.write_frame(&frame)
+            .map_err(io::Error::other)?;
+      }
+
+      Ok(())
+   }
+
+   pub fn write_apng<W: Write>(&self, out: W) -> io::Result<()> {
+      let (width, height) = self.size()?;
+
+      let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+      encoder.set_color(png::ColorType::Rgba);
+      encoder.set_depth(png::BitDepth::Eight);
+
+      let mut writer = encoder.set_animated(self.frames.len() as u32, 0)
+         .and_then(|_| encoder.set_frame_delay(self.delay, 100))
+         .and_then(|_| encoder.write_header())
+         .map_err(io::Error::other)?;
+
+      for image in self.frames.iter() {
+         let rgba: Vec<u8> = image.rgba().flatten().collect();
+         writer.write_image_data(&rgba)
+            .map_err(io::Error::other)?;
+      }
+
+      writer.finish()
+         .map_err(io::Error::other)
+   }
+
+   // every frame has to be the same size
+   fn size(&self) -> io::Result<(usize, usize)> {
+      let first = self.frames.first()
+         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "we didn't record any frames"))?;
+
+      if self.frames.iter().any(|frame| frame.width() != first.width() || frame.height() != first.height()) {
+         return Err(io::Error::new(io::ErrorKind::InvalidInput, "our frames aren't all the same size"));
+      }
+
+      Ok((first.width(), first.height()))
+   }
+
+   fn gif_size(&self) -> io::Result<(u16, u16)> {
+      let (width, height) = self.size()?;
+
+      if width > u16::MAX as usize || height > u16::MAX as usize {
+         return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{}x{} is too big for a GIF", width, height)));
+      }
+
+      Ok((width as u16, height as u16))
+   }
+
+   /// A GIF if our path ends in .gif, otherwise an animated PNG
+   pub fn save(&self, path: &str) -> Result<(), Error> {
+      let is_gif = Path::new(path).extension().map(|extension| extension == "gif").unwrap_or(false);
+
+      File::create(path)
+         .and_then(|file| {
+            let mut out = BufWriter::new(file);
+            if is_gif {
+               self.write_gif(&mut out)?;
+            } else {
+               self.write_apng(&mut out)?;
+            }
+
+            out.flush()
+         })
+         .map_err(|source| Error::Io { path: path.to_string(), source })
+   }
+}
\ No newline at end of file
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::Value;
+
+use crate::cli::Part;
+use crate::solution::Answers;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Status {
+   Pass,
+   Fail,
+   /// we don't know the answer yet
+   Unknown,
+}
+
+impl fmt::Display for Status {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Status::Pass => write!(f, "\u{001B}[32mpass\u{001B}[0m"),
+         Status::Fail => write!(f, "\u{001B}[31mfail\u{001B}[0m"),
+         Status::Unknown => write!(f, "\u{001B}[33munknown\u{001B}[0m"),
+      }
+   }
+}
+
+/// The answers we know for each of a day's input files, kept next to them in `answers.toml`
+///
+/// ```toml
+/// ["input.txt"]
+/// a = "576"
+/// b = "77674"
+/// ```
+pub struct AnswerFile {
+   path: PathBuf,
+   answers: BTreeMap<String, Answers>,
+}
+
+impl AnswerFile {
+   /// A missing file just means we don't know anything yet
+   pub fn load(day: &str) -> AnswerFile {
+      // handle my non-standard setup the same way we do for inputs
+      let path = if Path::new(day).is_dir() {
+         Path::new(day).join(ANSWERS_FILE)
+      } else {
+         PathBuf::from(ANSWERS_FILE)
+      };
+
+      let answers = match fs::read_to_string(&path) {
+         Ok(contents) => parse(&contents)
+            .unwrap_or_else(|message| panic!("{} is broken: {}", path.display(), message)),
+         Err(_) => BTreeMap::new(),
+      };
+
+      AnswerFile {
+         path,
+         answers,
+      }
+   }
+
+   pub fn path(&self) -> &Path {
+      &self.path
+   }
+
+   pub fn get(&self, name: &str) -> Option<&Answers> {
+      self.answers.get(name)
+   }
+
+   /// Fills in the parts we don't know yet, returning how many that was
+   pub fn record(&mut self, name: &str, answers: &Answers) -> usize {
+      let mut known = self.answers.get(name).cloned().unwrap_or_default();
+
+      let mut recorded = 0;
+      for part in [Part::A, Part::B].iter() {
+         if let (None, Some(answer)) = (known.get(*part), answers.get(*part)) {
+            known.set(*part, answer);
+            recorded += 1;
+         }
+      }
+
+      if recorded > 0 {
+         self.answers.insert(name.to_string(), known);
+      }
+
+      recorded
+   }
+
+   pub fn save(&self) -> io::Result<()> {
+      let mut contents = String::new();
+      for (name, answers) in self.answers.iter() {
+         if !contents.is_empty() {
+            contents.push('\n');
+         }
+         contents.push_str(&format!("[{}]\n", Value::String(name.clone())));
+
+         for part in [Part::A, Part::B].iter() {
+            if let Some(answer) = answers.get(*part) {
+               contents.push_str(&format!("{} = {}\n", part, quote(answer)));
+            }
+         }
+      }
+
+      fs::write(&self.path, contents)
+   }
+}
+
+// keep our pictures readable
+fn quote(answer: &str) -> String {
+   if answer.contains('\n') && !answer.contains("'''") {
+      format!("'''\n{}'''", answer)
+   } else {
+      Value::String(answer.to_string()).to_string()
+   }
+}
+
+fn parse(contents: &str) -> Result<BTreeMap<String, Answers>, String> {
+   let files = match contents.parse::<Value>().map_err(|e| e.to_string())? {
+      Value::Table(files) => files,
+      _ => return Err("expected a table of input files".to_string()),
+   };
+
+   let mut answers = BTreeMap::new();
+   for (name, parts) in files {
+      let parts = match parts {
+         Value::Table(parts) => parts,
+         _ => return Err(format!("expected a table of answers for {}", name)),
+      };
+
+      let mut file_answers = Answers::default();
+      for (part, answer) in parts {
+         let part = match part.as_ref() {
+            "a" => Part::A,
+            "b" => Part::B,
+            other => return Err(format!("unknown part {} for {}", other, name)),
+         };
+
+         // let numbers be written without their quotes
+         match answer {
+            Value::String(answer) => file_answers.set(part, answer),
+            Value::Integer(answer) => file_answers.set(part, answer),
+            other => return Err(format!("expected a string for part {} of {}, found {}", part, name, other)),
+         }
+      }
+
+      answers.insert(name, file_answers);
+   }
+
+   Ok(answers)
+}
+
+/// Shows where our answer went wrong, a line at a time for our answers that are pictures
+pub fn diff(expected: &str, answer: &str) -> String {
+   let expected: Vec<&str> = expected.lines().collect();
+   let answer: Vec<&str> = answer.lines().collect();
+
+   let mut diff = vec![];
+   for i in 0..expected.len().max(answer.len()) {
+      match (expected.get(i), answer.get(i)) {
+         (Some(e), Some(a)) if e == a => diff.push(format!("  {}", e)),
+         (e, a) => {
+            if let Some(e) = e {
+               diff.push(format!("\u{001B}[31m- {}\u{001B}[0m", e));
+            }
+            if let Some(a) = a {
+               diff.push(format!("\u{001B}[32m+ {}\u{001B}[0m", a));
+            }
+         }
+      }
+   }
+
+   diff.join("\n")
+}
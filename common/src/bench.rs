@@ -0,0 +1,268 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::Part;
+use crate::error::Error;
+use crate::solution::Timings;
+
+pub const HISTORY_FILE: &str = "bench_history.json";
+
+/// Anything this much slower than our baseline's median is flagged
+pub const REGRESSION_THRESHOLD: f64 = 0.1;
+
+/// How many times we run each part
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Bench {
+   /// untimed runs to warm up our caches first
+   pub warmups: usize,
+   pub runs: usize,
+   /// compare later benchmarks of this input against this one
+   pub baseline: bool,
+}
+
+impl Default for Bench {
+   fn default() -> Bench {
+      Bench {
+         warmups: 3,
+         runs: 10,
+         baseline: false,
+      }
+   }
+}
+
+/// Timing statistics in seconds
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Stats {
+   pub runs: usize,
+   pub mean: f64,
+   pub median: f64,
+   pub stddev: f64,
+}
+
+impl Stats {
+   pub fn new(times: &[Duration]) -> Stats {
+      let mut seconds: Vec<f64> = times.iter().map(|time| time.as_secs_f64()).collect();
+      seconds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+      let runs = seconds.len();
+      let mean = seconds.iter().sum::<f64>() / runs as f64;
+      let median = if runs.is_multiple_of(2) {
+         (seconds[runs / 2 - 1] + seconds[runs / 2]) / 2.0
+      } else {
+         seconds[runs / 2]
+      };
+      let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / runs as f64;
+
+      Stats {
+         runs,
+         mean,
+         median,
+         stddev: variance.sqrt(),
+      }
+   }
+}
+
+impl fmt::Display for Stats {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "mean {:>9}  median {:>9}  stddev {:>9}",
+             format_seconds(self.mean), format_seconds(self.median), format_seconds(self.stddev))
+   }
+}
+
+/// One benchmark of a day's input
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Entry {
+   /// seconds since the epoch
+   pub timestamp: u64,
+   pub day: String,
+   pub input: String,
+   pub parse: Stats,
+   pub a: Option<Stats>,
+   pub b: Option<Stats>,
+   /// we compare against the last baseline of our input, so a regression never becomes what we compare against
+   #[serde(default)]
+   pub baseline: bool,
+}
+
+impl Entry {
+   pub fn new(day: &str, input: &str, timings: &Timings, baseline: bool) -> Entry {
+      let stats = |times: &Vec<Duration>| if times.is_empty() { None } else { Some(Stats::new(times)) };
+
+      Entry {
+         timestamp: SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0),
+         day: day.to_string(),
+         input: input.to_string(),
+         parse: Stats::new(&timings.parse),
+         a: stats(&timings.a),
+         b: stats(&timings.b),
+         baseline,
+      }
+   }
+
+   pub fn get(&self, step: Step) -> Option<&Stats> {
+      match step {
+         Step::Parse => Some(&self.parse),
+         Step::Part(Part::A) => self.a.as_ref(),
+         Step::Part(Part::B) => self.b.as_ref(),
+      }
+   }
+}
+
+/// What we time separately
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Step {
+   Parse,
+   Part(Part),
+}
+
+pub const STEPS: [Step; 3] = [Step::Parse, Step::Part(Part::A), Step::Part(Part::B)];
+
+impl fmt::Display for Step {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Step::Parse => write!(f, "parse"),
+         Step::Part(part) => write!(f, "{}", part),
+      }
+   }
+}
+
+/// A step that got slower than our baseline by more than its noise
+#[derive(Clone, PartialEq, Debug)]
+pub struct Regression {
+   pub step: Step,
+   pub baseline: f64,
+   pub median: f64,
+}
+
+impl Regression {
+   pub fn change(&self) -> f64 {
+      (self.median - self.baseline) / self.baseline
+   }
+}
+
+impl fmt::Display for Regression {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{} regressed {:.0}% ({} -> {})",
+             self.step, self.change() * 100.0, format_seconds(self.baseline), format_seconds(self.median))
+   }
+}
+
+/// Compares each step's median to our baseline's, ignoring anything inside either one's spread
+pub fn regressions(baseline: &Entry, entry: &Entry) -> Vec<Regression> {
+   STEPS.iter()
+      .filter_map(|step| {
+         let (old, new) = (baseline.get(*step)?, entry.get(*step)?);
+         let noise = 2.0 * old.stddev.max(new.stddev);
+
+         if new.median > old.median * (1.0 + REGRESSION_THRESHOLD) && new.median - old.median > noise {
+            Some(Regression {
+               step: *step,
+               baseline: old.median,
+               median: new.median,
+            })
+         } else {
+            None
+         }
+      })
+      .collect()
+}
+
+/// Every benchmark we've run, oldest first
+pub struct History {
+   path: PathBuf,
+   entries: Vec<Entry>,
+}
+
+impl History {
+   /// Kept wherever we were run from, a missing file is an empty history
+   pub fn load() -> Result<History, Error> {
+      let path = PathBuf::from(HISTORY_FILE);
+      let error = |source| Error::Io { path: path.display().to_string(), source };
+
+      let entries = match fs::read_to_string(&path) {
+         Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| error(io::Error::new(io::ErrorKind::InvalidData, e)))?,
+         Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+         Err(e) => return Err(error(e)),
+      };
+
+      Ok(History {
+         path,
+         entries,
+      })
+   }
+
+   pub fn path(&self) -> &Path {
+      &self.path
+   }
+
+   /// The last benchmark of this input we made a baseline, or the first one we ran if we never did
+   pub fn baseline(&self, day: &str, input: &str) -> Option<&Entry> {
+      let mut entries = self.entries.iter()
+         .filter(|entry| entry.day == day && entry.input == input);
+      let first = entries.next()?;
+
+      Some(entries.rfind(|entry| entry.baseline).unwrap_or(first))
+   }
+
+   pub fn push(&mut self, entry: Entry) {
+      self.entries.push(entry);
+   }
+
+   pub fn save(&self) -> Result<(), Error> {
+      serde_json::to_string_pretty(&self.entries)
+         .map_err(io::Error::other)
+         .and_then(|contents| fs::write(&self.path, contents))
+         .map_err(|source| Error::Io { path: self.path.display().to_string(), source })
+   }
+}
+
+/// A benchmark and how it compares to our baseline
+pub struct Report {
+   pub entry: Entry,
+   pub baseline: Option<Entry>,
+   pub regressions: Vec<Regression>,
+}
+
+impl fmt::Display for Report {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      for step in STEPS.iter() {
+         if let Some(stats) = self.entry.get(*step) {
+            write!(f, "  {:<5}  {}", step.to_string(), stats)?;
+
+            if let Some(regression) = self.regressions.iter().find(|r| r.step == *step) {
+               write!(f, "  \u{001B}[31mregressed {:.0}% from {}\u{001B}[0m",
+                      regression.change() * 100.0, format_seconds(regression.baseline))?;
+            } else if let Some(old) = self.baseline.as_ref().and_then(|baseline| baseline.get(*step)) {
+               write!(f, "  was {}", format_seconds(old.median))?;
+            }
+
+            writeln!(f)?;
+         }
+      }
+
+      Ok(())
+   }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+   format_seconds(duration.as_secs_f64())
+}
+
+// pick whatever unit keeps the number readable
+pub fn format_seconds(seconds: f64) -> String {
+   if seconds >= 1.0 {
+      format!("{:.3}s", seconds)
+   } else if seconds >= 1e-3 {
+      format!("{:.3}ms", seconds * 1e3)
+   } else {
+      format!("{:.3}µs", seconds * 1e6)
+   }
+}
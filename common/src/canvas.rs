@@ -1,17 +1,22 @@
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::coordinates::Cells;
 use crate::coordinates::Grid;
 use crate::coordinates::OffsetLociX;
 use crate::coordinates::OffsetLociY;
+use crate::error::Error;
 
 pub fn render_grid<C, T>(pixel_size: usize, img_data: &mut [u32], grid: &Grid<T>, colorizer: &C) where
    C: Fn(&T) -> Option<u32> {
 
-   for grid_y in grid.y_range() {
-      for grid_x in grid.x_range() {
-         match colorizer(grid.get(grid_x, grid_y)) {
-            Some(color) => {
-               set_grid_square(grid_x, grid_y, color, pixel_size, img_data, grid);
-            }
-            None => {}
+   for (y, row) in grid.rows().enumerate() {
+      for (x, cell) in row.iter().enumerate() {
+         if let Some(color) = colorizer(cell) {
+            set_grid_square(grid.real_x(x), grid.real_y(y), color, pixel_size, img_data, grid);
          }
       }
    }
@@ -27,9 +32,10 @@ pub fn get_img_square_range(x: usize, y: usize, pixel_size: usize) -> (usize, us
 }
 
 pub fn set_grid_square<T>(grid_x: isize, grid_y: isize, color: u32, pixel_size: usize, img_data: &mut [u32], grid: &Grid<T>) {
-   let img_width = grid.width() * pixel_size;
-   let x = grid.raw_x(grid_x) as usize;
-   let y = grid.raw_y(grid_y) as usize;
+   set_img_square(grid.raw_x(grid_x), grid.raw_y(grid_y), color, pixel_size, grid.width() * pixel_size, img_data);
+}
+
+fn set_img_square(x: usize, y: usize, color: u32, pixel_size: usize, img_width: usize, img_data: &mut [u32]) {
    let (start_x, end_x, start_y, end_y) = get_img_square_range(x, y, pixel_size);
 
    for img_y in start_y..end_y {
@@ -44,4 +50,87 @@ pub fn set_img_pixel(img_x: usize, img_y: usize, color: u32, img_width: usize, i
 
    // flip our endianness to match the actual u8 byte array
    img_data[pixel] = color.to_be();
+}
+
+/// What `render_grid` would draw, but kept around so we can save it outside of the browser
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Image {
+   width: usize,
+   height: usize,
+   // the same byte order the browser gets, so each one is r, g, b, a in memory
+   pixels: Vec<u32>,
+}
+
+impl Image {
+   /// Any cell our colorizer skips is left transparent, or black in a PPM
+   pub fn render<G, T, C>(pixel_size: usize, grid: &G, colorizer: &C) -> Image where
+      G: Cells<T>,
+      C: Fn(&T) -> Option<u32> {
+      let width = grid.width() * pixel_size;
+      let height = grid.height() * pixel_size;
+      let mut pixels = vec![0; width * height];
+
+      for loci in grid.locis() {
+         if let Some(color) = colorizer(grid.get_loci(&loci)) {
+            set_img_square(grid.raw_x(loci.x()), grid.raw_y(loci.y()), color, pixel_size, width, &mut pixels);
+         }
+      }
+
+      Image {
+         width,
+         height,
+         pixels,
+      }
+   }
+
+   pub fn width(&self) -> usize {
+      self.width
+   }
+
+   pub fn height(&self) -> usize {
+      self.height
+   }
+
+   pub(crate) fn rgba(&self) -> impl Iterator<Item=[u8; 4]> + '_ {
+      self.pixels.iter().map(|pixel| pixel.to_ne_bytes())
+   }
+
+   /// A binary PPM, which has no alpha so that's dropped
+   pub fn write_ppm<W: Write>(&self, mut out: W) -> io::Result<()> {
+      write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+      let rgb: Vec<u8> = self.rgba()
+         .flat_map(|[r, g, b, _]| vec![r, g, b])
+         .collect();
+      out.write_all(&rgb)
+   }
+
+   pub fn write_png<W: Write>(&self, out: W) -> io::Result<()> {
+      let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+      encoder.set_color(png::ColorType::Rgba);
+      encoder.set_depth(png::BitDepth::Eight);
+
+      let rgba: Vec<u8> = self.rgba().flatten().collect();
+      encoder.write_header()
+         .and_then(|mut writer| writer.write_image_data(&rgba))
+         .map_err(io::Error::other)
+   }
+
+   /// A PPM if our path ends in .ppm, otherwise a PNG
+   pub fn save(&self, path: &str) -> Result<(), Error> {
+      let is_ppm = Path::new(path).extension().map(|extension| extension == "ppm").unwrap_or(false);
+
+      File::create(path)
+         .and_then(|file| {
+            let mut out = BufWriter::new(file);
+            if is_ppm {
+               self.write_ppm(&mut out)?;
+            } else {
+               self.write_png(&mut out)?;
+            }
+
+            out.flush()
+         })
+         .map_err(|source| Error::Io { path: path.to_string(), source })
+   }
 }
\ No newline at end of file
@@ -0,0 +1,221 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::process;
+use std::sync::OnceLock;
+
+use crate::animation;
+use crate::animation::Recorder;
+use crate::bench::Bench;
+use crate::error::Error;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Part {
+   A,
+   B,
+}
+
+impl fmt::Display for Part {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Part::A => write!(f, "a"),
+         Part::B => write!(f, "b"),
+      }
+   }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Input {
+   Stdin,
+   File(String),
+}
+
+impl Input {
+   pub fn name(&self) -> &str {
+      match self {
+         Input::Stdin => "stdin",
+         Input::File(path) => path,
+      }
+   }
+
+   pub fn read(&self) -> Result<String, Error> {
+      let mut contents = String::new();
+      match self {
+         Input::Stdin => io::stdin().read_to_string(&mut contents),
+         Input::File(path) => File::open(path)
+            .and_then(|mut f| f.read_to_string(&mut contents)),
+      }.map_err(|source| Error::Io { path: self.name().to_string(), source })?;
+
+      Ok(contents)
+   }
+}
+
+pub const USAGE: &str = "[--part a|b] [--input <path>|-] [--record] [--bench [--runs <n>] [--warmups <n>] [--baseline]] [--png <path>] [--animate <path> [--every <n>] [--max-mb <n>]]";
+
+/// What a day was asked to run from the command line
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Options {
+   /// only run this part, both when it's missing
+   pub part: Option<Part>,
+   /// run against this instead of our input.txt, our sample and test files are skipped
+   pub input: Option<Input>,
+   /// write the answers we didn't know yet to our answers.toml
+   pub record: bool,
+   /// time our input instead of checking our examples, `--runs`, `--warmups` and `--baseline` imply it
+   pub bench: Option<Bench>,
+   /// save a picture of our input here, `{}` is replaced with our day
+   pub png: Option<String>,
+   /// save an animation of our input's simulation
+   pub animate: Option<Animate>,
+}
+
+/// Where `--animate` saves to and how much of our simulation it keeps
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Animate {
+   /// `{}` is replaced with our day, a .gif is a GIF and anything else an animated PNG
+   pub path: String,
+   /// only keep a frame every so many ticks
+   pub every: usize,
+   /// the most our frames can take up before we start dropping them
+   pub max_mb: usize,
+}
+
+impl Animate {
+   pub fn recorder(&self) -> Recorder {
+      Recorder::new()
+         .every(self.every)
+         .max_bytes(self.max_mb * 1024 * 1024)
+   }
+}
+
+impl Options {
+   pub fn parse<I: Iterator<Item=String>>(mut args: I) -> Result<Options, String> {
+      let mut options = Options::default();
+      let mut every = None;
+      let mut max_mb = None;
+
+      while let Some(arg) = args.next() {
+         match arg.as_ref() {
+            "--part" | "-p" => {
+               options.part = match args.next().as_ref().map(|part| part.as_ref()) {
+                  Some("a") | Some("A") => Some(Part::A),
+                  Some("b") | Some("B") => Some(Part::B),
+                  Some(other) => return Err(format!("unknown part: {}", other)),
+                  None => return Err("--part needs a or b".to_string()),
+               };
+            }
+            "--input" | "-i" => {
+               options.input = match args.next() {
+                  Some(ref path) if path == "-" => Some(Input::Stdin),
+                  Some(path) => Some(Input::File(path)),
+                  None => return Err("--input needs a path or -".to_string()),
+               };
+            }
+            "--record" => options.record = true,
+            "--bench" | "-b" => {
+               options.bench.get_or_insert_with(Bench::default);
+            }
+            "--runs" => {
+               let runs = parse_count("--runs", args.next())?;
+               if runs == 0 {
+                  return Err("--runs needs at least one run".to_string());
+               }
+
+               options.bench.get_or_insert_with(Bench::default).runs = runs;
+            }
+            "--warmups" => {
+               options.bench.get_or_insert_with(Bench::default).warmups = parse_count("--warmups", args.next())?;
+            }
+            "--baseline" => options.bench.get_or_insert_with(Bench::default).baseline = true,
+            "--png" => {
+               options.png = match args.next() {
+                  Some(path) => Some(path),
+                  None => return Err("--png needs a path".to_string()),
+               };
+            }
+            "--animate" => {
+               options.animate = match args.next() {
+                  Some(path) => Some(Animate {
+                     path,
+                     every: 1,
+                     max_mb: animation::DEFAULT_MAX_BYTES / (1024 * 1024),
+                  }),
+                  None => return Err("--animate needs a path".to_string()),
+               };
+            }
+            "--every" => {
+               let ticks = parse_count("--every", args.next())?;
+               if ticks == 0 {
+                  return Err("--every needs at least one tick".to_string());
+               }
+
+               every = Some(ticks);
+            }
+            "--max-mb" => max_mb = Some(parse_count("--max-mb", args.next())?),
+            other => return Err(format!("unknown argument: {}", other)),
+         }
+      }
+
+      match options.animate.as_mut() {
+         Some(animate) => {
+            animate.every = every.unwrap_or(animate.every);
+            animate.max_mb = max_mb.unwrap_or(animate.max_mb);
+         }
+         None if every.is_some() || max_mb.is_some() => {
+            return Err("--every and --max-mb only work with --animate".to_string());
+         }
+         None => {}
+      }
+
+      Ok(options)
+   }
+
+   pub fn runs(&self, part: Part) -> bool {
+      self.part.map(|selected| selected == part).unwrap_or(true)
+   }
+}
+
+fn parse_count(flag: &str, count: Option<String>) -> Result<usize, String> {
+   match count {
+      Some(count) => count.parse()
+         .map_err(|_| format!("{} needs a number, not {}", flag, count)),
+      None => Err(format!("{} needs a number", flag)),
+   }
+}
+
+static OPTIONS: OnceLock<Options> = OnceLock::new();
+
+/// The options our binary was started with, we exit with our usage if they don't make sense
+pub fn options() -> &'static Options {
+   OPTIONS.get_or_init(|| {
+      Options::parse(env::args().skip(1))
+         .unwrap_or_else(|message| {
+            eprintln!("{}\nusage: {}", message, USAGE);
+            process::exit(2);
+         })
+   })
+}
+
+/// For a runner that reads its own arguments, this has to happen before anything is run
+pub fn set_options(options: Options) {
+   OPTIONS.set(options)
+      .expect("our options have already been read");
+}
+
+/// Our override input, read once so stdin can be used by more than one run, we exit if it can't be read
+pub fn override_input() -> Option<(&'static str, &'static str)> {
+   static CONTENTS: OnceLock<String> = OnceLock::new();
+
+   let input = options().input.as_ref()?;
+   let contents = CONTENTS.get_or_init(|| {
+      input.read()
+         .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(2);
+         })
+   });
+
+   Some((input.name(), contents))
+}
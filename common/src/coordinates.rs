@@ -1,15 +1,22 @@
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 use std::fmt;
+use std::slice;
 
-#[derive(Debug, Hash)]
+use crate::error::ParseError;
+use crate::geometry::Point;
+
+/// Our cells are kept a row at a time in one `Vec`, so a row is just a slice of it
+#[derive(Debug)]
 pub struct Grid<T> {
    width: usize,
    height: usize,
    x_offset: isize,
    y_offset: isize,
 
-   grid: Vec<Vec<T>>,
+   grid: Vec<T>,
 }
 
 pub trait OffsetLociX {
@@ -58,20 +65,54 @@ pub trait OffsetLociY {
    }
 }
 
+/// What every grid backend can do, so an algorithm doesn't care how its cells are stored
+pub trait Cells<T>: OffsetLociX + OffsetLociY {
+   fn get(&self, x: isize, y: isize) -> &T;
+
+   fn set(&mut self, x: isize, y: isize, value: T);
+
+   fn get_loci(&self, loci: &Loci) -> &T {
+      self.get(loci.x, loci.y)
+   }
+
+   fn set_loci(&mut self, loci: &Loci, value: T) {
+      self.set(loci.x, loci.y, value);
+   }
+
+   fn locis(&self) -> GridLocis {
+      GridLocis::new(self.width(), self.height(), self.x_min(), self.y_min())
+   }
+}
+
 impl<T> Grid<T> {
+   // we'd rather panic than let a stray x wrap onto the next row
+   #[inline]
+   fn index(&self, x: isize, y: isize) -> usize {
+      self.try_index(x, y)
+         .unwrap_or_else(|| panic!("{},{} is outside of our grid", x, y))
+   }
+
+   #[inline]
+   fn try_index(&self, x: isize, y: isize) -> Option<usize> {
+      if self.in_bounds(x, y) {
+         Some(self.raw_y(y) * self.width + self.raw_x(x))
+      } else {
+         None
+      }
+   }
+
    pub fn get_loci(&self, loci: &Loci) -> &T {
       self.get(loci.x, loci.y)
    }
 
    pub fn get(&self, x: isize, y: isize) -> &T {
-      &self.grid[self.raw_y(y)][self.raw_x(x)]
+      &self.grid[self.index(x, y)]
    }
 
    pub fn get_mut(&mut self, x: isize, y: isize) -> &mut T {
-      let raw_x = self.raw_x(x);
-      let raw_y = self.raw_y(y);
+      let i = self.index(x, y);
 
-      &mut self.grid[raw_y][raw_x]
+      &mut self.grid[i]
    }
 
    pub fn set_loci(&mut self, loci: &Loci, value: T) {
@@ -79,14 +120,69 @@ impl<T> Grid<T> {
    }
 
    pub fn set(&mut self, x: isize, y: isize, value: T) {
-      let raw_x = self.raw_x(x);
-      let raw_y = self.raw_y(y);
+      let i = self.index(x, y);
+
+      self.grid[i] = value
+   }
+
+   /// `None` when we're outside of the grid
+   pub fn try_get(&self, x: isize, y: isize) -> Option<&T> {
+      self.try_index(x, y).map(|i| &self.grid[i])
+   }
+
+   pub fn try_get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+      match self.try_index(x, y) {
+         Some(i) => Some(&mut self.grid[i]),
+         None => None,
+      }
+   }
+
+   /// Hands `value` back if we're outside of the grid
+   pub fn try_set(&mut self, x: isize, y: isize, value: T) -> Result<(), T> {
+      match self.try_get_mut(x, y) {
+         Some(cell) => {
+            *cell = value;
+            Ok(())
+         }
+         None => Err(value),
+      }
+   }
 
-      self.grid[raw_y][raw_x] = value
+   pub fn contains(&self, loci: &Loci) -> bool {
+      self.in_bounds(loci.x, loci.y)
+   }
+
+   fn in_bounds(&self, x: isize, y: isize) -> bool {
+      self.x_range().contains(&x) && self.y_range().contains(&y)
+   }
+
+   /// Every cell, a row at a time from the top
+   pub fn as_slice(&self) -> &[T] {
+      &self.grid
+   }
+
+   pub fn as_mut_slice(&mut self) -> &mut [T] {
+      &mut self.grid
+   }
+
+   pub fn row(&self, y: isize) -> &[T] {
+      let start = self.raw_y(y) * self.width;
+
+      &self.grid[start..start + self.width]
+   }
+
+   pub fn row_mut(&mut self, y: isize) -> &mut [T] {
+      let start = self.raw_y(y) * self.width;
+
+      &mut self.grid[start..start + self.width]
+   }
+
+   /// Our rows from the top, even when they're empty
+   pub fn rows(&self) -> impl Iterator<Item=&[T]> + '_ {
+      (0..self.height).map(move |raw_y| &self.grid[raw_y * self.width..(raw_y + 1) * self.width])
    }
 }
 
-//impl<T: fmt::Display> fmt::Display for Grid<T> {
 impl<T: fmt::Display> Grid<T> {
    pub fn print(&self) {
       for y in self.y_range() {
@@ -98,6 +194,67 @@ impl<T: fmt::Display> Grid<T> {
    }
 }
 
+/// A cell that can be read from, and written back to, a character map
+pub trait GridChar: Sized {
+   fn from_char(c: char) -> Option<Self>;
+
+   fn to_char(&self) -> char;
+}
+
+impl GridChar for char {
+   fn from_char(c: char) -> Option<char> {
+      Some(c)
+   }
+
+   fn to_char(&self) -> char {
+      *self
+   }
+}
+
+impl<T: GridChar> Grid<T> {
+   /// A line for each of our rows, `parse_ascii` reads it back
+   pub fn to_ascii(&self) -> String {
+      let mut ascii = String::with_capacity((self.width + 1) * self.height);
+      for row in self.rows() {
+         ascii.extend(row.iter().map(GridChar::to_char));
+         ascii.push('\n');
+      }
+
+      ascii
+   }
+}
+
+impl<T: GridChar + Clone> Grid<T> {
+   /// Reads a character map a line at a time, any line shorter than the longest is filled out with `padding`
+   pub fn parse_ascii(contents: &str, padding: T) -> Result<Grid<T>, ParseError> {
+      Grid::parse_ascii_offset(contents, padding, 0, 0)
+   }
+
+   /// Like `parse_ascii` but the first character of our map is at `x_offset`, `y_offset`
+   pub fn parse_ascii_offset(contents: &str, padding: T, x_offset: isize, y_offset: isize) -> Result<Grid<T>, ParseError> {
+      let width = contents.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+      let height = contents.lines().count();
+
+      let mut grid = Grid::new_offset(padding, width, height, x_offset, y_offset);
+      for (raw_y, line) in contents.lines().enumerate() {
+         for (raw_x, c) in line.chars().enumerate() {
+            let cell = T::from_char(c)
+               .ok_or_else(|| ParseError::new(raw_y + 1, raw_x + 1, format!("unexpected {:?} in our map", c)))?;
+
+            grid.set(grid.real_x(raw_x), grid.real_y(raw_y), cell);
+         }
+      }
+
+      Ok(grid)
+   }
+}
+
+impl<T: GridChar> fmt::Display for Grid<T> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      f.write_str(&self.to_ascii())
+   }
+}
+
 impl<T: Clone> Grid<T> {
    pub fn new(default: T, width: usize, height: usize) -> Grid<T> {
       Grid::new_offset(default, width, height, 0, 0)
@@ -113,7 +270,7 @@ impl<T: Clone> Grid<T> {
          height,
          x_offset,
          y_offset,
-         grid: vec![vec![default; width]; height],
+         grid: vec![default; width * height],
       }
    }
 
@@ -127,8 +284,73 @@ impl<T: Clone> Grid<T> {
 
    pub fn enumerate(&self) -> GridEnumerator<'_, T> {
       GridEnumerator {
-         iter: self.into_iter()
+         locis: GridLocis::for_grid(self),
+         iter: self.grid.iter(),
+      }
+   }
+
+   /// Grows us just enough that `loci` is inside, filling our new cells with `fill`
+   pub fn grow_to_include(&mut self, loci: &Loci, fill: T) {
+      if self.in_bounds(loci.x, loci.y) {
+         return;
+      }
+
+      let (min, max) = if self.grid.is_empty() {
+         (*loci, loci.add(1, 1))
+      } else {
+         (Loci::new(self.x_min().min(loci.x), self.y_min().min(loci.y)),
+          Loci::new(self.x_max().max(loci.x + 1), self.y_max().max(loci.y + 1)))
+      };
+
+      self.reframe(&min, &max, fill);
+   }
+
+   /// Trims us down to the cells that aren't `default`, we're left empty if they all are
+   pub fn shrink_to_fit(&mut self, default: &T) where
+      T: PartialEq {
+      let mut bounds: Option<(Loci, Loci)> = None;
+      for (loci, cell) in self.enumerate() {
+         if cell != default {
+            bounds = Some(match bounds {
+               Some((min, max)) => (min.min_x(loci.x).min_y(loci.y), max.max_x(loci.x + 1).max_y(loci.y + 1)),
+               None => (loci, loci.add(1, 1)),
+            });
+         }
+      }
+
+      let offset = Loci::new(self.x_min(), self.y_min());
+      let (min, max) = bounds.unwrap_or((offset, offset));
+
+      self.reframe(&min, &max, default.clone());
+   }
+
+   // moves us to cover min up to an exclusive max, keeping the cells we had there
+   fn reframe(&mut self, min: &Loci, max: &Loci, fill: T) {
+      let width = (max.x - min.x).max(0) as usize;
+      let height = (max.y - min.y).max(0) as usize;
+      let x_from = self.x_min().max(min.x).min(max.x);
+      let x_to = self.x_max().min(max.x).max(x_from);
+
+      let mut grid = Vec::with_capacity(width * height);
+      for y in min.y..max.y {
+         if !self.y_range().contains(&y) || x_from == x_to {
+            grid.resize(grid.len() + width, fill.clone());
+            continue;
+         }
+
+         let row = self.row(y);
+         grid.resize(grid.len() + (x_from - min.x) as usize, fill.clone());
+         grid.extend_from_slice(&row[self.raw_x(x_from)..self.raw_x(x_to)]);
+         grid.resize(grid.len() + (max.x - x_to) as usize, fill.clone());
       }
+
+      *self = Grid {
+         width,
+         height,
+         x_offset: min.x,
+         y_offset: min.y,
+         grid,
+      };
    }
 }
 
@@ -152,14 +374,38 @@ impl<T> OffsetLociY for Grid<T> {
    }
 }
 
+impl<T> Cells<T> for Grid<T> {
+   fn get(&self, x: isize, y: isize) -> &T {
+      Grid::get(self, x, y)
+   }
+
+   fn set(&mut self, x: isize, y: isize, value: T) {
+      Grid::set(self, x, y, value)
+   }
+}
+
 impl<'a, T> IntoIterator for &'a Grid<T> {
    type Item = &'a T;
    type IntoIter = GridIterator<'a, T>;
 
    fn into_iter(self) -> Self::IntoIter {
       GridIterator {
-         grid: &self,
-         locis: GridLocis::for_grid(self),
+         iter: self.grid.iter(),
+      }
+   }
+}
+
+// hashes the same as when we were a Vec of rows
+impl<T: Hash> Hash for Grid<T> {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      self.width.hash(state);
+      self.height.hash(state);
+      self.x_offset.hash(state);
+      self.y_offset.hash(state);
+
+      state.write_usize(self.height);
+      for row in self.rows() {
+         row.hash(state);
       }
    }
 }
@@ -176,37 +422,286 @@ impl<T: Clone> Clone for Grid<T> {
    }
 }
 
-pub struct GridIterator<'a, T> {
-   grid: &'a Grid<T>,
+/// Every cell is `default` until we write to it, we grow to fit whatever we write
+#[derive(Debug, Clone, Hash)]
+pub struct InfiniteGrid<T> {
+   grid: Grid<T>,
+   default: T,
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+   pub fn new(default: T) -> InfiniteGrid<T> {
+      InfiniteGrid {
+         grid: Grid::new(default.clone(), 0, 0),
+         default,
+      }
+   }
+
+   pub fn get_loci(&self, loci: &Loci) -> &T {
+      self.get(loci.x, loci.y)
+   }
+
+   pub fn get(&self, x: isize, y: isize) -> &T {
+      if self.grid.in_bounds(x, y) {
+         self.grid.get(x, y)
+      } else {
+         &self.default
+      }
+   }
+
+   pub fn set_loci(&mut self, loci: &Loci, value: T) {
+      self.set(loci.x, loci.y, value);
+   }
+
+   pub fn set(&mut self, x: isize, y: isize, value: T) {
+      if !self.grid.in_bounds(x, y) {
+         self.grow(x, y);
+      }
+
+      self.grid.set(x, y, value);
+   }
+
+   // grow by at least half again on the sides we need, so writing a line of cells isn't quadratic
+   fn grow(&mut self, x: isize, y: isize) {
+      let grid = &self.grid;
+      if grid.grid.is_empty() {
+         let loci = Loci::new(x, y);
+         return self.grid.reframe(&loci, &loci.add(1, 1), self.default.clone());
+      }
+
+      let slack_x = (grid.width / 2).max(1) as isize;
+      let slack_y = (grid.height / 2).max(1) as isize;
+
+      let min = Loci::new(
+         if x < grid.x_min() { x.min(grid.x_min() - slack_x) } else { grid.x_min() },
+         if y < grid.y_min() { y.min(grid.y_min() - slack_y) } else { grid.y_min() },
+      );
+      let max = Loci::new(
+         if x >= grid.x_max() { (x + 1).max(grid.x_max() + slack_x) } else { grid.x_max() },
+         if y >= grid.y_max() { (y + 1).max(grid.y_max() + slack_y) } else { grid.y_max() },
+      );
+
+      self.grid.reframe(&min, &max, self.default.clone());
+   }
+
+   /// Trims what we've allocated down to the cells that aren't our default
+   pub fn shrink_to_fit(&mut self) where
+      T: PartialEq {
+      self.grid.shrink_to_fit(&self.default);
+   }
+
+   /// The cells we've allocated so far, anything outside of them is our default
+   pub fn grid(&self) -> &Grid<T> {
+      &self.grid
+   }
+
+   pub fn into_grid(self) -> Grid<T> {
+      self.grid
+   }
+}
+
+impl<T> OffsetLociX for InfiniteGrid<T> {
+   fn width(&self) -> usize {
+      self.grid.width
+   }
+
+   fn x_min(&self) -> isize {
+      self.grid.x_offset
+   }
+}
+
+impl<T> OffsetLociY for InfiniteGrid<T> {
+   fn height(&self) -> usize {
+      self.grid.height
+   }
+
+   fn y_min(&self) -> isize {
+      self.grid.y_offset
+   }
+}
+
+impl<T: Clone> Cells<T> for InfiniteGrid<T> {
+   fn get(&self, x: isize, y: isize) -> &T {
+      InfiniteGrid::get(self, x, y)
+   }
+
+   fn set(&mut self, x: isize, y: isize, value: T) {
+      InfiniteGrid::set(self, x, y, value)
+   }
+}
+
+/// Only the cells we've written are stored, for coordinates spread too far apart to allocate between.
+/// Our bounds are the smallest box around everything we've written, and everything else is `default`
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+   cells: HashMap<Loci, T>,
+   default: T,
+   min: Loci,
+   // exclusive
+   max: Loci,
+}
+
+impl<T> SparseGrid<T> {
+   pub fn new(default: T) -> SparseGrid<T> {
+      SparseGrid {
+         cells: HashMap::new(),
+         default,
+         min: Loci::new(0, 0),
+         max: Loci::new(0, 0),
+      }
+   }
+
+   pub fn get_loci(&self, loci: &Loci) -> &T {
+      self.cells.get(loci).unwrap_or(&self.default)
+   }
+
+   pub fn get(&self, x: isize, y: isize) -> &T {
+      self.get_loci(&Loci::new(x, y))
+   }
+
+   pub fn set_loci(&mut self, loci: &Loci, value: T) {
+      if self.cells.is_empty() {
+         self.min = *loci;
+         self.max = loci.add(1, 1);
+      } else {
+         self.min = self.min.min_x(loci.x).min_y(loci.y);
+         self.max = self.max.max_x(loci.x + 1).max_y(loci.y + 1);
+      }
+
+      self.cells.insert(*loci, value);
+   }
+
+   pub fn set(&mut self, x: isize, y: isize, value: T) {
+      self.set_loci(&Loci::new(x, y), value);
+   }
+
+   /// How many cells we've actually written
+   pub fn len(&self) -> usize {
+      self.cells.len()
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.cells.is_empty()
+   }
+
+   pub fn locis(&self) -> GridLocis {
+      GridLocis::new(self.width(), self.height(), self.x_min(), self.y_min())
+   }
+
+   /// Every cell in our bounds a row at a time from the top, like `Grid::iter`
+   pub fn iter(&self) -> SparseIterator<'_, T> {
+      self.into_iter()
+   }
+
+   pub fn enumerate(&self) -> SparseEnumerator<'_, T> {
+      SparseEnumerator {
+         locis: self.locis(),
+         grid: self,
+      }
+   }
+
+   /// Only the cells we've written, in no particular order
+   pub fn cells(&self) -> impl Iterator<Item=(&Loci, &T)> + '_ {
+      self.cells.iter()
+   }
+}
+
+impl<T> OffsetLociX for SparseGrid<T> {
+   fn width(&self) -> usize {
+      (self.max.x - self.min.x) as usize
+   }
+
+   fn x_min(&self) -> isize {
+      self.min.x
+   }
+}
+
+impl<T> OffsetLociY for SparseGrid<T> {
+   fn height(&self) -> usize {
+      (self.max.y - self.min.y) as usize
+   }
+
+   fn y_min(&self) -> isize {
+      self.min.y
+   }
+}
+
+impl<T> Cells<T> for SparseGrid<T> {
+   fn get(&self, x: isize, y: isize) -> &T {
+      SparseGrid::get(self, x, y)
+   }
+
+   fn set(&mut self, x: isize, y: isize, value: T) {
+      SparseGrid::set(self, x, y, value)
+   }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+   type Item = &'a T;
+   type IntoIter = SparseIterator<'a, T>;
+
+   fn into_iter(self) -> Self::IntoIter {
+      SparseIterator {
+         enumerator: self.enumerate(),
+      }
+   }
+}
+
+pub struct SparseIterator<'a, T> {
+   enumerator: SparseEnumerator<'a, T>,
+}
+
+impl<'a, T> Iterator for SparseIterator<'a, T> {
+   type Item = &'a T;
+
+   fn next(&mut self) -> Option<Self::Item> {
+      self.enumerator.next().map(|(_, cell)| cell)
+   }
+}
+
+pub struct SparseEnumerator<'a, T> {
    locis: GridLocis,
+   grid: &'a SparseGrid<T>,
+}
+
+impl<'a, T> Iterator for SparseEnumerator<'a, T> {
+   type Item = (Loci, &'a T);
+
+   fn next(&mut self) -> Option<Self::Item> {
+      self.locis.next().map(|loci| (loci, self.grid.get_loci(&loci)))
+   }
+}
+
+pub struct GridIterator<'a, T> {
+   iter: slice::Iter<'a, T>,
 }
 
 impl<'a, T> Iterator for GridIterator<'a, T> {
    type Item = &'a T;
 
    fn next(&mut self) -> Option<Self::Item> {
-      self.locis.next()
-         .map(|loci| {
-            self.grid.get_loci(&loci)
-         })
+      self.iter.next()
+   }
+
+   fn size_hint(&self) -> (usize, Option<usize>) {
+      self.iter.size_hint()
    }
 }
 
 pub struct GridEnumerator<'a, T> {
-   iter: GridIterator<'a, T>
+   locis: GridLocis,
+   iter: slice::Iter<'a, T>,
 }
 
 impl<'a, T> Iterator for GridEnumerator<'a, T> {
    type Item = (Loci, &'a T);
 
    fn next(&mut self) -> Option<Self::Item> {
-      let loci = self.iter.locis.loci();
-
-      self.iter.next().map(|result| (loci.unwrap(), result))
+      self.locis.next().zip(self.iter.next())
    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GridLocis {
    x: isize,
    y: isize,
@@ -323,8 +818,7 @@ impl Loci {
    }
 
    pub fn distance(&self, other: &Loci) -> usize {
-      ((self.x() - other.x()).abs() +
-         (self.y() - other.y()).abs()) as usize
+      Point::from(*self).distance(&Point::from(*other))
    }
 
    #[inline]
@@ -334,7 +828,7 @@ impl Loci {
 
    #[inline]
    pub fn add_loci(&self, other: &Loci) -> Loci {
-      self.add(other.x, other.y)
+      (Point::from(*self) + Point::from(*other)).into()
    }
 
    #[inline]
@@ -354,7 +848,7 @@ impl Loci {
 
    #[inline]
    pub fn sub_loci(&self, other: &Loci) -> Loci {
-      self.sub(other.x, other.y)
+      (Point::from(*self) - Point::from(*other)).into()
    }
 
    #[inline]
@@ -389,26 +883,24 @@ impl Loci {
 
    #[inline]
    pub fn valid_neighbors<T>(&self, grid: &Grid<T>) -> Vec<Loci> {
-      self.neighbors().iter()
-         .filter_map(|neighbor| {
-            if grid.x_min() <= neighbor.x() && neighbor.x() < grid.x_max() &&
-               grid.y_min() <= neighbor.y() && neighbor.y() < grid.y_max() {
-               Some(*neighbor)
-            } else {
-               None
-            }
-         })
-         .collect()
+      self.orthogonal().within(grid).collect()
    }
 
    #[inline]
    pub fn neighbors(&self) -> Vec<Loci> {
-      vec![
-         self.add_x(1),
-         self.sub_x(1),
-         self.add_y(1),
-         self.sub_y(1),
-      ]
+      self.orthogonal().collect()
+   }
+}
+
+impl From<Loci> for Point<2> {
+   fn from(loci: Loci) -> Point<2> {
+      Point::new([loci.x, loci.y])
+   }
+}
+
+impl From<Point<2>> for Loci {
+   fn from(point: Point<2>) -> Loci {
+      Loci::new(point[0], point[1])
    }
 }
 
@@ -0,0 +1,98 @@
+use std::error;
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::str::FromStr;
+
+/// Why we couldn't run an input
+#[derive(Debug)]
+pub enum Error {
+   /// we couldn't find or read one of our input files
+   Io {
+      path: String,
+      source: io::Error,
+   },
+   Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Error::Io { path, source } => write!(f, "couldn't read {}: {}", path, source),
+         Error::Parse(e) => write!(f, "{}", e),
+      }
+   }
+}
+
+impl error::Error for Error {
+   fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+      match self {
+         Error::Io { source, .. } => Some(source),
+         Error::Parse(e) => Some(e),
+      }
+   }
+}
+
+impl From<ParseError> for Error {
+   fn from(e: ParseError) -> Error {
+      Error::Parse(e)
+   }
+}
+
+/// Where our input stopped making sense, lines and columns count from 1
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+   /// our parsers only see contents, whoever read them fills this in
+   pub file: Option<String>,
+   pub line: usize,
+   pub column: usize,
+   pub reason: String,
+}
+
+impl ParseError {
+   pub fn new<D: Display>(line: usize, column: usize, reason: D) -> ParseError {
+      ParseError {
+         file: None,
+         line,
+         column,
+         reason: reason.to_string(),
+      }
+   }
+
+   /// Points at `part`, which has to be a slice of `contents` like our lines, splits and captures are
+   pub fn at<D: Display>(contents: &str, part: &str, reason: D) -> ParseError {
+      let start = contents.as_ptr() as usize;
+      let offset = (part.as_ptr() as usize).wrapping_sub(start);
+      debug_assert!(offset <= contents.len(), "{:?} isn't part of our contents", part);
+
+      let before = &contents[..offset.min(contents.len())];
+      let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+      ParseError::new(before.matches('\n').count() + 1, before[line_start..].chars().count() + 1, reason)
+   }
+
+   pub fn in_file(mut self, file: &str) -> ParseError {
+      self.file.get_or_insert_with(|| file.to_string());
+      self
+   }
+}
+
+impl fmt::Display for ParseError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      if let Some(file) = &self.file {
+         write!(f, "{}:", file)?;
+      }
+
+      write!(f, "{}:{}: {}", self.line, self.column, self.reason)
+   }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses a slice of our contents, pointing at it when it isn't a `T`
+pub fn parse_at<T>(contents: &str, part: &str) -> Result<T, ParseError> where
+   T: FromStr,
+   T::Err: Display {
+   part.parse()
+      .map_err(|e| ParseError::at(contents, part, format!("can't parse {:?}: {}", part, e)))
+}
@@ -0,0 +1,212 @@
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Sub};
+
+/// A point in `N` dimensions, everything is measured in Manhattan distance
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Point<const N: usize>([isize; N]);
+
+impl<const N: usize> Point<N> {
+   pub const fn new(coordinates: [isize; N]) -> Point<N> {
+      Point(coordinates)
+   }
+
+   pub const fn splat(value: isize) -> Point<N> {
+      Point([value; N])
+   }
+
+   pub const fn origin() -> Point<N> {
+      Point::splat(0)
+   }
+
+   pub const fn min_value() -> Point<N> {
+      Point::splat(isize::MIN)
+   }
+
+   pub const fn max_value() -> Point<N> {
+      Point::splat(isize::MAX)
+   }
+
+   pub fn coordinates(&self) -> &[isize; N] {
+      &self.0
+   }
+
+   pub fn distance(&self, other: &Point<N>) -> usize {
+      self.0.iter().zip(other.0.iter())
+         .map(|(a, b)| (a - b).unsigned_abs())
+         .sum()
+   }
+
+   /// The smallest of each of our coordinates
+   pub fn min(&self, other: &Point<N>) -> Point<N> {
+      self.zip_with(other, |a, b| a.min(b))
+   }
+
+   /// The largest of each of our coordinates
+   pub fn max(&self, other: &Point<N>) -> Point<N> {
+      self.zip_with(other, |a, b| a.max(b))
+   }
+
+   fn zip_with<F>(&self, other: &Point<N>, f: F) -> Point<N> where
+      F: Fn(isize, isize) -> isize {
+      let mut coordinates = self.0;
+      for (coordinate, other) in coordinates.iter_mut().zip(other.0.iter()) {
+         *coordinate = f(*coordinate, *other);
+      }
+
+      Point(coordinates)
+   }
+}
+
+impl<const N: usize> Add for Point<N> {
+   type Output = Point<N>;
+
+   fn add(self, other: Point<N>) -> Point<N> {
+      self.zip_with(&other, |a, b| a + b)
+   }
+}
+
+impl<const N: usize> Sub for Point<N> {
+   type Output = Point<N>;
+
+   fn sub(self, other: Point<N>) -> Point<N> {
+      self.zip_with(&other, |a, b| a - b)
+   }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+   type Output = isize;
+
+   fn index(&self, axis: usize) -> &isize {
+      &self.0[axis]
+   }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+   fn index_mut(&mut self, axis: usize) -> &mut isize {
+      &mut self.0[axis]
+   }
+}
+
+impl<const N: usize> From<[isize; N]> for Point<N> {
+   fn from(coordinates: [isize; N]) -> Point<N> {
+      Point(coordinates)
+   }
+}
+
+impl<const N: usize> fmt::Display for Point<N> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "(")?;
+      for (axis, coordinate) in self.0.iter().enumerate() {
+         if axis > 0 {
+            write!(f, ", ")?;
+         }
+         write!(f, "{}", coordinate)?;
+      }
+      write!(f, ")")
+   }
+}
+
+/// An axis aligned box from `min` up to an exclusive `max`
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Bounds<const N: usize> {
+   min: Point<N>,
+   max: Point<N>,
+}
+
+impl<const N: usize> Bounds<N> {
+   pub fn new(min: Point<N>, max: Point<N>) -> Bounds<N> {
+      Bounds {
+         min,
+         max,
+      }
+   }
+
+   /// The smallest box holding every one of our points, or `None` if we don't have any
+   pub fn around<'a, I>(points: I) -> Option<Bounds<N>> where
+      I: IntoIterator<Item=&'a Point<N>> {
+      points.into_iter()
+         .fold(None, |bounds: Option<Bounds<N>>, point| Some(match bounds {
+            Some(bounds) => Bounds::new(bounds.min.min(point), bounds.max.max(&(*point + Point::splat(1)))),
+            None => Bounds::new(*point, *point + Point::splat(1)),
+         }))
+   }
+
+   pub fn min(&self) -> &Point<N> {
+      &self.min
+   }
+
+   pub fn max(&self) -> &Point<N> {
+      &self.max
+   }
+
+   /// How far we reach along `axis`
+   pub fn len(&self, axis: usize) -> usize {
+      (self.max[axis] - self.min[axis]).max(0) as usize
+   }
+
+   pub fn is_empty(&self) -> bool {
+      (0..N).any(|axis| self.len(axis) == 0)
+   }
+
+   pub fn volume(&self) -> u128 {
+      (0..N).map(|axis| self.len(axis) as u128).product()
+   }
+
+   /// The middle of each axis, rounded towards our min
+   pub fn mid(&self) -> Point<N> {
+      self.min.zip_with(&self.max, |min, max| (max - min) / 2 + min)
+   }
+
+   pub fn contains(&self, point: &Point<N>) -> bool {
+      (0..N).all(|axis| self.min[axis] <= point[axis] && point[axis] < self.max[axis])
+   }
+
+   pub fn intersect(&self, other: &Bounds<N>) -> Option<Bounds<N>> {
+      let intersection = Bounds::new(self.min.max(&other.min), self.max.min(&other.max));
+
+      if intersection.is_empty() {
+         None
+      } else {
+         Some(intersection)
+      }
+   }
+
+   /// Halves us along every axis, skipping the halves that would be empty.
+   /// The first axis changes slowest, like nesting a loop for each axis
+   pub fn split(&self) -> Vec<Bounds<N>> {
+      let mid = self.mid();
+
+      let mut split = vec![*self];
+      for axis in 0..N {
+         split = split.iter()
+            .flat_map(|bounds| {
+               let mut lower = *bounds;
+               lower.max[axis] = mid[axis];
+               let mut upper = *bounds;
+               upper.min[axis] = mid[axis];
+
+               vec![lower, upper]
+            })
+            .filter(|bounds| bounds.len(axis) > 0)
+            .collect();
+      }
+
+      split
+   }
+
+   /// Every combination of our min and max coordinates, with our max being exclusive some of these sit just outside of us
+   pub fn corners(&self) -> Vec<Point<N>> {
+      (0..1usize << N)
+         .map(|corner| {
+            let mut point = self.min;
+            for axis in 0..N {
+               if corner & (1 << (N - 1 - axis)) != 0 {
+                  point[axis] = self.max[axis];
+               }
+            }
+
+            point
+         })
+         .collect()
+   }
+}
\ No newline at end of file
@@ -1,84 +1,366 @@
 use std::fs::File;
 use std::io::prelude::*;
-use std::fmt::Debug;
-use std::time::{Instant, Duration};
 
 pub mod coordinates;
+pub mod geometry;
+pub mod neighborhood;
+pub mod search;
+pub mod threshold;
 pub mod wasm;
 pub mod canvas;
+pub mod animation;
+pub mod cli;
 
-pub fn run_day<R>(day: &str, runner: &R) where
-   R: Fn(&String, bool) {
-   run_day_sample(day, runner);
+pub mod solution;
+pub mod answers;
+pub mod bench;
+pub mod error;
 
-   run_day_real(day, runner);
+pub use cli::Part;
+pub use answers::{AnswerFile, Status};
+pub use bench::{Bench, Report};
+pub use error::{parse_at, Error, ParseError};
+pub use solution::{Answers, Day, Example, Solution, Solved, Source, Timings};
+
+/// One input run through a day, along with what we expected from it
+pub struct Run {
+   pub day: String,
+   pub name: String,
+   pub is_sample: bool,
+   /// the parts we were asked to run
+   pub parts: Vec<Part>,
+   pub solved: Result<Solved, Error>,
+   pub expected: Answers,
+   /// only when we were asked to benchmark
+   pub benchmark: Option<Report>,
 }
 
-// for when we want to skip our sample tests
-pub fn run_day_real<R>(day: &str, runner: &R) where
-   R: Fn(&String, bool) {
-   run_input(day, "input.txt", &|contents| runner(contents, false));
+impl Run {
+   /// How a part we ran compares to what we expected, every part fails if we couldn't read or parse our input
+   pub fn status(&self, part: Part) -> Option<Status> {
+      if !self.parts.contains(&part) {
+         return None;
+      }
+
+      let answer = match &self.solved {
+         Ok(solved) => solved.answers.get(part)?,
+         Err(_) => return Some(Status::Fail),
+      };
+
+      Some(match self.expected.get(part) {
+         Some(expected) if expected == answer => Status::Pass,
+         Some(_) => Status::Fail,
+         None => Status::Unknown,
+      })
+   }
+
+   /// The parts that didn't give the answer we expected
+   pub fn mismatches(&self) -> Vec<Part> {
+      [Part::A, Part::B].iter()
+         .filter(|part| self.status(**part) == Some(Status::Fail))
+         .cloned()
+         .collect()
+   }
+
+   pub fn passed(&self) -> bool {
+      self.mismatches().is_empty()
+   }
 }
 
-// for when we're not ready to run the full thing
-pub fn run_day_sample<R>(day: &str, runner: &R) where
-   R: Fn(&String, bool) {
-   run_input(day, "sample_input.txt", &|contents| runner(contents, true));
+/// Runs a day from its own binary, failing if any of our answers weren't what we expected
+pub fn run_main(day: &str, solution: &dyn Day) {
+   let runs = run_day(day, solution);
+
+   if !runs.iter().all(|run| run.passed()) {
+      std::process::exit(1);
+   }
 }
 
-pub fn run_input<R>(day: &str, file_name: &str, runner: &R) where
-   R: Fn(&String) {
-   let input = read_input(day, file_name);
+pub fn run_day(day: &str, solution: &dyn Day) -> Vec<Run> {
+   let mut answer_file = AnswerFile::load(day);
+
+   // benchmarks only time our real input
+   let mut runs = if cli::options().bench.is_some() {
+      vec![]
+   } else {
+      run_tests(day, solution, &answer_file)
+   };
+
+   runs.extend(run_day_real(day, solution, &answer_file));
+
+   // we only know the answers for our own input
+   if cli::options().record && cli::override_input().is_none() {
+      let mut recorded = 0;
+      for run in runs.iter() {
+         let solved = match &run.solved {
+            Ok(solved) => solved,
+            Err(_) => continue,
+         };
+
+         let mut unknown = Answers::default();
+         for part in [Part::A, Part::B].iter() {
+            if run.status(*part) == Some(Status::Unknown) {
+               unknown.set(*part, solved.answers.get(*part).unwrap());
+            }
+         }
+
+         recorded += answer_file.record(&run.name, &unknown);
+      }
+
+      if recorded > 0 {
+         answer_file.save()
+            .unwrap_or_else(|e| panic!("couldn't write {}: {}", answer_file.path().display(), e));
+
+         println!("Recorded {} answers in {}", recorded, answer_file.path().display());
+      }
+   }
+
+   runs
+}
+
+// with --input we run that instead of our input.txt
+pub fn run_day_real(day: &str, solution: &dyn Day, answer_file: &AnswerFile) -> Option<Run> {
+   let (name, contents, expected) = match cli::override_input() {
+      Some((name, contents)) => (name.to_string(), Ok(contents.to_string()), Answers::default()),
+      None => {
+         let expected = answer_file.get("input.txt").cloned().unwrap_or_default();
+
+         ("input.txt".to_string(), read_input(day, "input.txt"), expected)
+      }
+   };
+
+   let contents = match contents {
+      Ok(contents) => contents,
+      Err(error) => return run_unreadable(day, &name, false, None, error, solution),
+   };
+
+   let mut run = run_input(day, &name, &contents, false, None, &expected, solution)?;
+
+   if let (Some(bench), true) = (cli::options().bench, run.solved.is_ok()) {
+      match run_bench(day, &name, &contents, bench, &run.parts, solution) {
+         Ok(report) => run.benchmark = Some(report),
+         Err(error) => print_error(&error),
+      }
+   }
+
+   if let (Some(path), true) = (&cli::options().png, run.solved.is_ok()) {
+      save_image(day, path, &contents, solution);
+   }
+
+   if let (Some(animate), true) = (&cli::options().animate, run.solved.is_ok()) {
+      save_animation(day, animate, &contents, solution);
+   }
+
+   Some(run)
+}
+
+/// Saves a picture of our input for `--png`, `{}` in our path is replaced with our day
+pub fn save_image(day: &str, path: &str, contents: &str, solution: &dyn Day) {
+   let path = path.replace("{}", day);
+
+   let image = solution.render(contents, false)
+      .expect("we parsed this input a moment ago");
+   match image.map(|image| image.save(&path)) {
+      Some(Ok(())) => println!("Saved {}", path),
+      Some(Err(error)) => print_error(&error),
+      None => println!("Day {} doesn't have a picture to save", day),
+   }
+}
+
+/// Saves our input's simulation for `--animate`, `{}` in our path is replaced with our day
+pub fn save_animation(day: &str, animate: &cli::Animate, contents: &str, solution: &dyn Day) {
+   let path = animate.path.replace("{}", day);
+
+   let mut recorder = animate.recorder();
+   let animated = solution.animate(contents, false, &mut recorder)
+      .expect("we parsed this input a moment ago");
+   if !animated {
+      println!("Day {} doesn't have a simulation to animate", day);
+      return;
+   }
+
+   match recorder.save(&path) {
+      Ok(()) => println!("Saved {} ({} frames over {} ticks)", path, recorder.frames().len(), recorder.ticks()),
+      Err(error) => print_error(&error),
+   }
+}
+
+/// Times our input, comparing it to the last time we did and adding it to our history
+pub fn run_bench(day: &str, name: &str, contents: &str, bench: Bench, parts: &[Part], solution: &dyn Day) -> Result<Report, Error> {
+   println!("Benchmarking {}: {} ({} runs after {} warmups)", day, name, bench.runs, bench.warmups);
+
+   let timings = solution.bench(contents, false, parts, bench)
+      .expect("we parsed this input a moment ago");
+   let entry = bench::Entry::new(day, name, &timings, bench.baseline);
+
+   let mut history = bench::History::load()?;
+   let baseline = history.baseline(day, name).cloned();
+   let regressions = baseline.as_ref()
+      .map(|baseline| bench::regressions(baseline, &entry))
+      .unwrap_or_default();
+
+   history.push(entry.clone());
+   history.save()?;
+
+   let report = Report {
+      entry,
+      baseline,
+      regressions,
+   };
+   print!("{}", report);
+
+   Ok(report)
+}
+
+// our examples only make sense against our own input
+pub fn run_tests(day: &str, solution: &dyn Day, answer_file: &AnswerFile) -> Vec<Run> {
+   if cli::override_input().is_some() {
+      return vec![];
+   }
+
+   solution.examples().iter()
+      .filter_map(|example| {
+         let contents = match &example.source {
+            Source::File(file_name) => read_input(day, file_name),
+            Source::Text(contents) => Ok(contents.clone()),
+         };
+         let contents = match contents {
+            Ok(contents) => contents,
+            Err(error) => return run_unreadable(day, example.name(), true, example.part, error, solution),
+         };
+
+         // the answers in our code come first, the answer file knows the rest
+         let mut expected = example.expected.clone();
+         if let Some(known) = answer_file.get(example.name()) {
+            expected.a = expected.a.or_else(|| known.a.clone());
+            expected.b = expected.b.or_else(|| known.b.clone());
+         }
+
+         run_input(day, example.name(), &contents, true, example.part, &expected, solution)
+      })
+      .collect()
+}
+
+/// Runs the parts we were asked for, nothing happens if that doesn't leave any
+pub fn run_input(day: &str,
+                 name: &str,
+                 contents: &str,
+                 is_sample: bool,
+                 only: Option<Part>,
+                 expected: &Answers,
+                 solution: &dyn Day) -> Option<Run> {
+   let parts = selected_parts(solution, only);
+   if parts.is_empty() {
+      return None;
+   }
 
    // give our output a random color
    let random_color_index = (rand::random::<u8>() % 5) + 2;
    let color = format!("\u{001B}[3{}m", random_color_index);
 
-   println!("{}Running {}:\u{001B}[0m {}", color, day, file_name);
+   println!("{}Running {}:\u{001B}[0m {}", color, day, name);
+
+   let solved = solution.solve(contents, is_sample, &parts)
+      .map_err(|e| Error::Parse(e.in_file(name)));
+
+   let run = Run {
+      day: day.to_string(),
+      name: name.to_string(),
+      is_sample,
+      parts,
+      solved,
+      expected: expected.clone(),
+      benchmark: None,
+   };
+
+   let solved = match &run.solved {
+      Ok(solved) => solved,
+      Err(error) => {
+         print_error(error);
+         return Some(run);
+      }
+   };
+
+   for part in run.parts.iter() {
+      if let (Some(answer), Some(status)) = (solved.answers.get(*part), run.status(*part)) {
+         print_answer(&format!("Result {} [{}]:", part.to_string().to_uppercase(), status), answer);
+
+         if status == Status::Fail {
+            println!("{}", answers::diff(run.expected.get(*part).unwrap(), answer));
+         }
+      }
+   }
 
-   let elapsed = benchmark(|| {
-      runner(&input);
-   });
+   println!("{}Finished In:\u{001B}[0m {} (parse {}, solve {})",
+            color,
+            bench::format_duration(solved.parse_time + solved.solve_time),
+            bench::format_duration(solved.parse_time),
+            bench::format_duration(solved.solve_time));
 
-   println!("{}Finished In:\u{001B}[0m {:01}.{:03}s", color, elapsed.as_secs(), elapsed.subsec_millis());
+   Some(run)
 }
 
-pub fn run_tests<C, R: Eq + Debug>(day: &str,
-                                   file_format: &str,
-                                   expected: Vec<R>,
-                                   runner: &C) where
-   C: Fn(&String) -> R {
-   for i in 1..=expected.len() {
-      let e = &expected[i - 1];
-      let file_name_segments: Vec<&str> = file_format.split("{}").collect();
-      let file_name = file_name_segments.join(i.to_string().as_str());
-
-      run_input(day, file_name.as_str(), &|contents| {
-         assert_eq!(*e, runner(contents));
-      })
+/// Fails every part we would have run on an input we couldn't read
+pub fn run_unreadable(day: &str,
+                      name: &str,
+                      is_sample: bool,
+                      only: Option<Part>,
+                      error: Error,
+                      solution: &dyn Day) -> Option<Run> {
+   let parts = selected_parts(solution, only);
+   if parts.is_empty() {
+      return None;
    }
+
+   println!("Running {}: {}", day, name);
+   print_error(&error);
+
+   Some(Run {
+      day: day.to_string(),
+      name: name.to_string(),
+      is_sample,
+      parts,
+      solved: Err(error),
+      expected: Answers::default(),
+      benchmark: None,
+   })
 }
 
-pub fn benchmark<C>(runner: C) -> Duration where
-   C: Fn() {
-   let now = Instant::now();
-   runner();
-   return now.elapsed();
+// the parts the day has, that the example is for and that we were asked to run
+fn selected_parts(solution: &dyn Day, only: Option<Part>) -> Vec<Part> {
+   solution.parts().iter()
+      .filter(|part| only.map(|only| only == **part).unwrap_or(true))
+      .filter(|part| cli::options().runs(**part))
+      .cloned()
+      .collect()
+}
+
+fn print_error(error: &Error) {
+   println!("\u{001B}[31mError:\u{001B}[0m {}", error);
+}
+
+// some answers are pictures, so start them on their own line
+fn print_answer(label: &str, answer: &str) {
+   if answer.contains('\n') {
+      println!("{}\n{}", label, answer);
+   } else {
+      println!("{} {}", label, answer);
+   }
 }
 
-pub fn read_input(day: &str, file_name: &str) -> String {
-   let mut f = load_input(day, file_name);
+pub fn read_input(day: &str, file_name: &str) -> Result<String, Error> {
+   let mut f = load_input(day, file_name)?;
 
    let mut contents = String::new();
    f.read_to_string(&mut contents)
-      .expect("something went wrong reading the file");
+      .map_err(|source| Error::Io { path: file_name.to_string(), source })?;
 
-   return contents;
+   Ok(contents)
 }
 
 // handle my non-standard setup
-pub fn load_input(day: &str, file_name: &str) -> File {
-   return File::open(file_name)
-      .or(File::open(format!("{}/{}", day, file_name)))
-      .expect(format!("file not found: {}", file_name).as_ref());
+pub fn load_input(day: &str, file_name: &str) -> Result<File, Error> {
+   File::open(file_name)
+      .or_else(|_| File::open(format!("{}/{}", day, file_name)))
+      .map_err(|source| Error::Io { path: file_name.to_string(), source })
 }
\ No newline at end of file
@@ -0,0 +1,175 @@
+use std::iter::Copied;
+use std::ops::Range;
+use std::slice;
+
+use crate::coordinates::{Grid, Loci, OffsetLociX, OffsetLociY};
+
+/// Right, left, down and then up, the same order `Loci::neighbors` has always had
+pub const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// The 8 cells around us in reading order
+pub const ADJACENT: [(isize, isize); 8] = [
+   (-1, -1), (0, -1), (1, -1),
+   (-1, 0), (1, 0),
+   (-1, 1), (0, 1), (1, 1),
+];
+
+pub type Offsets<'a> = Copied<slice::Iter<'a, (isize, isize)>>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Metric {
+   Manhattan,
+   Chebyshev,
+}
+
+impl Metric {
+   fn within(self, x: isize, y: isize, radius: isize) -> bool {
+      match self {
+         Metric::Manhattan => x.abs() + y.abs() <= radius,
+         Metric::Chebyshev => x.abs().max(y.abs()) <= radius,
+      }
+   }
+}
+
+/// Every cell some offset away from a center, without allocating anything
+#[derive(Clone, Debug)]
+pub struct Neighbors<I> {
+   center: Loci,
+   offsets: I,
+}
+
+impl<I> Neighbors<I> {
+   /// Skips anything outside of `bounds`
+   pub fn within<B: OffsetLociX + OffsetLociY>(self, bounds: &B) -> Clipped<Self> {
+      Clipped {
+         iter: self,
+         x_range: bounds.x_range(),
+         y_range: bounds.y_range(),
+      }
+   }
+}
+
+impl<I: Iterator<Item=(isize, isize)>> Iterator for Neighbors<I> {
+   type Item = Loci;
+
+   fn next(&mut self) -> Option<Self::Item> {
+      let center = self.center;
+
+      self.offsets.next().map(|(x, y)| center.add(x, y))
+   }
+
+   fn size_hint(&self) -> (usize, Option<usize>) {
+      self.offsets.size_hint()
+   }
+}
+
+#[derive(Clone, Debug)]
+pub struct Clipped<I> {
+   iter: I,
+   x_range: Range<isize>,
+   y_range: Range<isize>,
+}
+
+impl<I: Iterator<Item=Loci>> Iterator for Clipped<I> {
+   type Item = Loci;
+
+   fn next(&mut self) -> Option<Self::Item> {
+      let (x_range, y_range) = (&self.x_range, &self.y_range);
+
+      self.iter.find(|loci| x_range.contains(&loci.x()) && y_range.contains(&loci.y()))
+   }
+
+   fn size_hint(&self) -> (usize, Option<usize>) {
+      (0, self.iter.size_hint().1)
+   }
+}
+
+/// The offsets within a radius of us in reading order, leaving out the center
+#[derive(Clone, Debug)]
+pub struct RadiusOffsets {
+   radius: isize,
+   metric: Metric,
+   x: isize,
+   y: isize,
+}
+
+impl RadiusOffsets {
+   pub fn new(radius: usize, metric: Metric) -> RadiusOffsets {
+      let radius = radius as isize;
+
+      RadiusOffsets {
+         radius,
+         metric,
+         x: -radius,
+         y: -radius,
+      }
+   }
+}
+
+impl Iterator for RadiusOffsets {
+   type Item = (isize, isize);
+
+   fn next(&mut self) -> Option<Self::Item> {
+      while self.y <= self.radius {
+         let offset = (self.x, self.y);
+
+         self.x += 1;
+         if self.x > self.radius {
+            self.x = -self.radius;
+            self.y += 1;
+         }
+
+         if offset != (0, 0) && self.metric.within(offset.0, offset.1, self.radius) {
+            return Some(offset);
+         }
+      }
+
+      None
+   }
+}
+
+impl Loci {
+   /// Our 4 neighbors, see `ORTHOGONAL` for the order
+   pub fn orthogonal(&self) -> Neighbors<Offsets<'static>> {
+      self.stencil(&ORTHOGONAL)
+   }
+
+   /// Our 8 neighbors, including the diagonals
+   pub fn adjacent(&self) -> Neighbors<Offsets<'static>> {
+      self.stencil(&ADJACENT)
+   }
+
+   /// Everything within `radius` steps of us, not counting ourselves
+   pub fn manhattan(&self, radius: usize) -> Neighbors<RadiusOffsets> {
+      self.radius(radius, Metric::Manhattan)
+   }
+
+   /// Everything in the square `radius` cells out from us, not counting ourselves
+   pub fn chebyshev(&self, radius: usize) -> Neighbors<RadiusOffsets> {
+      self.radius(radius, Metric::Chebyshev)
+   }
+
+   pub fn radius(&self, radius: usize, metric: Metric) -> Neighbors<RadiusOffsets> {
+      Neighbors {
+         center: *self,
+         offsets: RadiusOffsets::new(radius, metric),
+      }
+   }
+
+   /// Each of `offsets` away from us, in the order they're given
+   pub fn stencil<'a>(&self, offsets: &'a [(isize, isize)]) -> Neighbors<Offsets<'a>> {
+      Neighbors {
+         center: *self,
+         offsets: offsets.iter().copied(),
+      }
+   }
+}
+
+impl<T> Grid<T> {
+   /// The cells of `neighbors` that are on our grid
+   pub fn neighborhood<'a, I>(&'a self, neighbors: Neighbors<I>) -> impl Iterator<Item=(Loci, &'a T)> + 'a where
+      I: Iterator<Item=(isize, isize)> + 'a {
+      neighbors.within(self)
+         .map(move |loci| (loci, self.get_loci(&loci)))
+   }
+}
\ No newline at end of file
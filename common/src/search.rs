@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// A search from `start` over whatever `successors` says we can step to, built up like
+///
+/// `Search::new(start, successors).cost(c).heuristic(h).tie_break(key).until(is_goal).astar()`
+///
+/// Every step costs 1 unless we're given a `cost`. Without `until` we search everything we can reach
+pub struct Search<S, N, C, H, T, G> {
+   start: S,
+   successors: N,
+   cost: C,
+   heuristic: H,
+   tie_break: T,
+   is_goal: G,
+}
+
+impl<S, N> Search<S, N, fn(&S, &S) -> usize, fn(&S) -> usize, fn(&S), fn(&S) -> bool> {
+   pub fn new(start: S, successors: N) -> Self {
+      Search {
+         start,
+         successors,
+         cost: |_, _| 1,
+         heuristic: |_| 0,
+         tie_break: |_| (),
+         is_goal: |_| false,
+      }
+   }
+}
+
+impl<S, N, C, H, T, G> Search<S, N, C, H, T, G> {
+   /// What it costs to step from one state to the next, `bfs` ignores this
+   pub fn cost<C2: FnMut(&S, &S) -> usize>(self, cost: C2) -> Search<S, N, C2, H, T, G> {
+      Search {
+         start: self.start,
+         successors: self.successors,
+         cost,
+         heuristic: self.heuristic,
+         tie_break: self.tie_break,
+         is_goal: self.is_goal,
+      }
+   }
+
+   /// Our guess at the cost left to a goal, only `astar` uses this and it can't ever guess too high
+   pub fn heuristic<H2: FnMut(&S) -> usize>(self, heuristic: H2) -> Search<S, N, C, H2, T, G> {
+      Search {
+         start: self.start,
+         successors: self.successors,
+         cost: self.cost,
+         heuristic,
+         tie_break: self.tie_break,
+         is_goal: self.is_goal,
+      }
+   }
+
+   /// States that are equally far along are visited lowest key first.
+   /// So with `bfs` and `dijkstra` the first goal we find, and the predecessor each state keeps, are the ones with the lowest key.
+   /// `astar` orders by its estimate first, so it only breaks ties between states with the same estimate
+   pub fn tie_break<K: Ord, T2: FnMut(&S) -> K>(self, tie_break: T2) -> Search<S, N, C, H, T2, G> {
+      Search {
+         start: self.start,
+         successors: self.successors,
+         cost: self.cost,
+         heuristic: self.heuristic,
+         tie_break,
+         is_goal: self.is_goal,
+      }
+   }
+
+   /// Stops at the first goal we visit
+   pub fn until<G2: FnMut(&S) -> bool>(self, is_goal: G2) -> Search<S, N, C, H, T, G2> {
+      Search {
+         start: self.start,
+         successors: self.successors,
+         cost: self.cost,
+         heuristic: self.heuristic,
+         tie_break: self.tie_break,
+         is_goal,
+      }
+   }
+}
+
+impl<S, N, I, C, H, T, K, G> Search<S, N, C, H, T, G> where
+   S: Hash + Eq + Clone,
+   N: FnMut(&S) -> I,
+   I: IntoIterator<Item=S>,
+   C: FnMut(&S, &S) -> usize,
+   H: FnMut(&S) -> usize,
+   T: FnMut(&S) -> K,
+   K: Ord,
+   G: FnMut(&S) -> bool {
+   /// A layer at a time, counting every step as 1
+   pub fn bfs(mut self) -> Paths<S> {
+      let mut paths = Paths::new(self.start.clone());
+
+      let mut layer = vec![self.start];
+      let mut distance = 0;
+      while !layer.is_empty() {
+         let tie_break = &mut self.tie_break;
+         layer.sort_by_cached_key(|state| tie_break(state));
+
+         let mut next_layer = vec![];
+         for state in layer {
+            if (self.is_goal)(&state) {
+               paths.goal = Some(state);
+               return paths;
+            }
+
+            for next in (self.successors)(&state) {
+               if !paths.distances.contains_key(&next) {
+                  paths.distances.insert(next.clone(), distance + 1);
+                  paths.predecessors.insert(next.clone(), state.clone());
+                  next_layer.push(next);
+               }
+            }
+         }
+
+         layer = next_layer;
+         distance += 1;
+      }
+
+      paths
+   }
+
+   pub fn dijkstra(mut self) -> Paths<S> {
+      self.best_first(false)
+   }
+
+   pub fn astar(mut self) -> Paths<S> {
+      self.best_first(true)
+   }
+
+   fn best_first(&mut self, use_heuristic: bool) -> Paths<S> {
+      let mut paths = Paths::new(self.start.clone());
+
+      let mut open = BinaryHeap::new();
+      open.push(Open {
+         priority: if use_heuristic { (self.heuristic)(&self.start) } else { 0 },
+         key: (self.tie_break)(&self.start),
+         cost: 0,
+         state: self.start.clone(),
+      });
+
+      while let Some(Open { cost, state, .. }) = open.pop() {
+         // we've already found a cheaper way here
+         if cost > paths.distances[&state] {
+            continue;
+         }
+
+         if (self.is_goal)(&state) {
+            paths.goal = Some(state);
+            return paths;
+         }
+
+         for next in (self.successors)(&state) {
+            let next_cost = cost + (self.cost)(&state, &next);
+
+            if paths.distances.get(&next).is_none_or(|known| next_cost < *known) {
+               let estimate = if use_heuristic { (self.heuristic)(&next) } else { 0 };
+
+               paths.distances.insert(next.clone(), next_cost);
+               paths.predecessors.insert(next.clone(), state.clone());
+               open.push(Open {
+                  priority: next_cost + estimate,
+                  key: (self.tie_break)(&next),
+                  cost: next_cost,
+                  state: next,
+               });
+            }
+         }
+      }
+
+      paths
+   }
+}
+
+/// Everything a search found, how far away each state was and how we got there
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+   start: S,
+   goal: Option<S>,
+   distances: HashMap<S, usize>,
+   predecessors: HashMap<S, S>,
+}
+
+impl<S: Hash + Eq + Clone> Paths<S> {
+   fn new(start: S) -> Paths<S> {
+      let mut distances = HashMap::new();
+      distances.insert(start.clone(), 0);
+
+      Paths {
+         start,
+         goal: None,
+         distances,
+         predecessors: HashMap::new(),
+      }
+   }
+
+   pub fn start(&self) -> &S {
+      &self.start
+   }
+
+   /// The goal we stopped at, if we found one
+   pub fn goal(&self) -> Option<&S> {
+      self.goal.as_ref()
+   }
+
+   pub fn distance(&self, state: &S) -> Option<usize> {
+      self.distances.get(state).cloned()
+   }
+
+   /// Every state we reached, a search that stopped early can have some here that were never visited
+   pub fn distances(&self) -> &HashMap<S, usize> {
+      &self.distances
+   }
+
+   pub fn predecessors(&self) -> &HashMap<S, S> {
+      &self.predecessors
+   }
+
+   /// From our start to `state`, including both
+   pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+      if !self.distances.contains_key(state) {
+         return None;
+      }
+
+      let mut path = vec![state.clone()];
+      while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+         path.push(previous.clone());
+      }
+      path.reverse();
+
+      Some(path)
+   }
+
+   /// From our start to our goal, including both
+   pub fn path(&self) -> Option<Vec<S>> {
+      self.goal.as_ref().and_then(|goal| self.path_to(goal))
+   }
+}
+
+// what's left to visit, cheapest and then lowest key first
+struct Open<S, K> {
+   priority: usize,
+   key: K,
+   cost: usize,
+   state: S,
+}
+
+impl<S, K: Ord> PartialEq for Open<S, K> {
+   fn eq(&self, other: &Open<S, K>) -> bool {
+      self.cmp(other) == Ordering::Equal
+   }
+}
+
+impl<S, K: Ord> Eq for Open<S, K> {}
+
+impl<S, K: Ord> PartialOrd for Open<S, K> {
+   fn partial_cmp(&self, other: &Open<S, K>) -> Option<Ordering> {
+      Some(self.cmp(other))
+   }
+}
+
+impl<S, K: Ord> Ord for Open<S, K> {
+   // flipped since our BinaryHeap pops the largest
+   fn cmp(&self, other: &Open<S, K>) -> Ordering {
+      other.priority.cmp(&self.priority)
+         .then_with(|| other.key.cmp(&self.key))
+   }
+}
\ No newline at end of file
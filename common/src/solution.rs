@@ -0,0 +1,254 @@
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::animation::Recorder;
+use crate::bench::Bench;
+use crate::canvas::Image;
+use crate::cli::Part;
+use crate::error::ParseError;
+
+/// A day's puzzle, parsed once and then answered a part at a time
+pub trait Solution {
+   type Input;
+   type A: Display;
+   type B: Display;
+
+   /// the last day only has a part a
+   const PARTS: &'static [Part] = &[Part::A, Part::B];
+
+   fn parse(&self, contents: &str, is_sample: bool) -> Result<Self::Input, ParseError>;
+
+   fn part_a(&self, input: &Self::Input) -> Self::A;
+
+   fn part_b(&self, input: &Self::Input) -> Self::B;
+
+   /// Days that work out both parts at once override this so we only do it once
+   fn both(&self, input: &Self::Input) -> (Self::A, Self::B) {
+      (self.part_a(input), self.part_b(input))
+   }
+
+   /// What we check before running our input.txt
+   fn examples(&self) -> Vec<Example> {
+      vec![Example::sample()]
+   }
+
+   /// A picture of our input once it's solved, for `--png`
+   fn render(&self, _input: &Self::Input) -> Option<Image> {
+      None
+   }
+
+   /// Runs our simulation a tick at a time into `recorder`, for `--animate`.
+   /// False for the days that don't have one
+   fn animate(&self, _input: &Self::Input, _recorder: &mut Recorder) -> bool {
+      false
+   }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Answers {
+   pub a: Option<String>,
+   pub b: Option<String>,
+}
+
+impl Answers {
+   pub fn get(&self, part: Part) -> Option<&String> {
+      match part {
+         Part::A => self.a.as_ref(),
+         Part::B => self.b.as_ref(),
+      }
+   }
+
+   pub fn set<D: Display>(&mut self, part: Part, answer: D) {
+      let answer = Some(answer.to_string());
+      match part {
+         Part::A => self.a = answer,
+         Part::B => self.b = answer,
+      }
+   }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Source {
+   File(String),
+   Text(String),
+}
+
+/// Input we know the answers to
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Example {
+   pub source: Source,
+   /// only run this part, some parts need their own sample or don't work on one at all
+   pub part: Option<Part>,
+   pub expected: Answers,
+}
+
+impl Example {
+   pub fn file(file_name: &str) -> Example {
+      Example {
+         source: Source::File(file_name.to_string()),
+         part: None,
+         expected: Answers::default(),
+      }
+   }
+
+   pub fn sample() -> Example {
+      Example::file("sample_input.txt")
+   }
+
+   pub fn text(contents: &str) -> Example {
+      Example {
+         source: Source::Text(contents.to_string()),
+         part: None,
+         expected: Answers::default(),
+      }
+   }
+
+   /// One example per answer, `{}` in the file format is replaced with 1, 2, 3...
+   pub fn numbered<D: Display>(file_format: &str, part: Part, expected: Vec<D>) -> Vec<Example> {
+      expected.iter().enumerate()
+         .map(|(i, answer)| {
+            let mut example = Example::file(&file_format.replace("{}", &(i + 1).to_string()))
+               .only(part);
+            example.expected.set(part, answer);
+
+            example
+         })
+         .collect()
+   }
+
+   pub fn only(mut self, part: Part) -> Example {
+      self.part = Some(part);
+      self
+   }
+
+   pub fn a<D: Display>(mut self, answer: D) -> Example {
+      self.expected.set(Part::A, answer);
+      self
+   }
+
+   pub fn b<D: Display>(mut self, answer: D) -> Example {
+      self.expected.set(Part::B, answer);
+      self
+   }
+
+   pub fn name(&self) -> &str {
+      match &self.source {
+         Source::File(file_name) => file_name,
+         Source::Text(contents) => contents,
+      }
+   }
+}
+
+pub struct Solved {
+   pub answers: Answers,
+   pub parse_time: Duration,
+   pub solve_time: Duration,
+}
+
+/// How long each of our timed runs took
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Timings {
+   pub parse: Vec<Duration>,
+   pub a: Vec<Duration>,
+   pub b: Vec<Duration>,
+}
+
+/// A `Solution` without its types so every day can sit in the same registry
+pub trait Day {
+   fn parts(&self) -> &'static [Part];
+
+   fn examples(&self) -> Vec<Example>;
+
+   fn solve(&self, contents: &str, is_sample: bool, parts: &[Part]) -> Result<Solved, ParseError>;
+
+   /// Times parsing and each part on their own, even for days that work out both at once
+   fn bench(&self, contents: &str, is_sample: bool, parts: &[Part], bench: Bench) -> Result<Timings, ParseError>;
+
+   fn render(&self, contents: &str, is_sample: bool) -> Result<Option<Image>, ParseError>;
+
+   fn animate(&self, contents: &str, is_sample: bool, recorder: &mut Recorder) -> Result<bool, ParseError>;
+}
+
+impl<S: Solution> Day for S {
+   fn parts(&self) -> &'static [Part] {
+      S::PARTS
+   }
+
+   fn examples(&self) -> Vec<Example> {
+      Solution::examples(self)
+   }
+
+   fn solve(&self, contents: &str, is_sample: bool, parts: &[Part]) -> Result<Solved, ParseError> {
+      let now = Instant::now();
+      let input = self.parse(contents, is_sample)?;
+      let parse_time = now.elapsed();
+
+      let now = Instant::now();
+      let mut answers = Answers::default();
+      match parts {
+         [Part::A, Part::B] => {
+            let (a, b) = self.both(&input);
+            answers.set(Part::A, a);
+            answers.set(Part::B, b);
+         }
+         _ => {
+            for part in parts {
+               match part {
+                  Part::A => answers.set(Part::A, self.part_a(&input)),
+                  Part::B => answers.set(Part::B, self.part_b(&input)),
+               }
+            }
+         }
+      }
+
+      Ok(Solved {
+         answers,
+         parse_time,
+         solve_time: now.elapsed(),
+      })
+   }
+
+   fn bench(&self, contents: &str, is_sample: bool, parts: &[Part], bench: Bench) -> Result<Timings, ParseError> {
+      let mut timings = Timings::default();
+
+      for run in 0..bench.warmups + bench.runs {
+         let timed = run >= bench.warmups;
+
+         let now = Instant::now();
+         let input = black_box(self.parse(contents, is_sample)?);
+         if timed {
+            timings.parse.push(now.elapsed());
+         }
+
+         for part in parts {
+            let now = Instant::now();
+            match part {
+               Part::A => drop(black_box(self.part_a(&input))),
+               Part::B => drop(black_box(self.part_b(&input))),
+            }
+
+            if timed {
+               match part {
+                  Part::A => timings.a.push(now.elapsed()),
+                  Part::B => timings.b.push(now.elapsed()),
+               }
+            }
+         }
+      }
+
+      Ok(timings)
+   }
+
+   fn render(&self, contents: &str, is_sample: bool) -> Result<Option<Image>, ParseError> {
+      let input = self.parse(contents, is_sample)?;
+
+      Ok(Solution::render(self, &input))
+   }
+
+   fn animate(&self, contents: &str, is_sample: bool, recorder: &mut Recorder) -> Result<bool, ParseError> {
+      let input = self.parse(contents, is_sample)?;
+
+      Ok(Solution::animate(self, &input, recorder))
+   }
+}
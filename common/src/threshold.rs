@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::thread;
+
+/// The smallest parameter that won, and what it won with
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Threshold<T> {
+   pub parameter: isize,
+   pub outcome: T,
+   /// false when something we tried lost after a smaller parameter won
+   pub monotonic: bool,
+   /// how many parameters we had to try
+   pub evaluated: usize,
+}
+
+/// Finds the smallest of our `parameters` that `win` gives an outcome for, trying a batch at a time on every core.
+///
+/// We gallop out from our start, each gap twice the last, until something wins.
+/// A winner could still be hiding between the losers below that, so we then try everything below it in order,
+/// which always finds the smallest winner however many cores we have.
+/// Anything we tried above it that lost means winners don't keep winning, which `monotonic` tells us
+pub fn min_winning<T, F>(parameters: Range<isize>, win: F) -> Option<Threshold<T>> where
+   T: Send,
+   F: Fn(isize) -> Option<T> + Sync {
+   let threads = thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+   let mut search = ThresholdSearch {
+      parameters,
+      results: BTreeMap::new(),
+      threads,
+      win,
+   };
+
+   search.find()
+}
+
+struct ThresholdSearch<T, F> {
+   parameters: Range<isize>,
+   results: BTreeMap<isize, Option<T>>,
+   threads: usize,
+   win: F,
+}
+
+impl<T, F> ThresholdSearch<T, F> where
+   T: Send,
+   F: Fn(isize) -> Option<T> + Sync {
+   fn find(&mut self) -> Option<Threshold<T>> {
+      let end = self.parameters.end;
+      if self.parameters.is_empty() {
+         return None;
+      }
+
+      // gallop out from our start until something wins, each gap twice the last
+      let mut next = self.parameters.start;
+      let mut gap: isize = 1;
+      let known = loop {
+         let mut batch = vec![];
+         while batch.len() < self.threads && next < end {
+            batch.push(next);
+            next = next.saturating_add(gap);
+            gap = gap.saturating_mul(2);
+         }
+
+         // make sure we've tried our last parameter before we give up
+         let exhausted = next >= end;
+         if exhausted && batch.last() != Some(&(end - 1)) {
+            batch.push(end - 1);
+         }
+
+         self.evaluate(&batch);
+         if let Some(winner) = self.smallest_winner(self.parameters.clone()) {
+            break winner;
+         }
+
+         if exhausted {
+            return None;
+         }
+      };
+
+      self.smallest(known)
+   }
+
+   // the smallest winner from our start, trying everything below our smallest known one in order
+   fn smallest(&mut self, known: isize) -> Option<Threshold<T>> {
+      let mut next = self.parameters.start;
+      while next < known {
+         let batch: Vec<isize> = (next..known)
+            .filter(|parameter| !self.results.contains_key(parameter))
+            .take(self.threads)
+            .collect();
+         self.evaluate(&batch);
+
+         let until = batch.last().map(|last| last + 1).unwrap_or(known);
+         if let Some(winner) = self.smallest_winner(next..until) {
+            return self.threshold(winner);
+         }
+
+         next = until;
+      }
+
+      self.threshold(known)
+   }
+
+   fn smallest_winner(&self, parameters: Range<isize>) -> Option<isize> {
+      self.results.range(parameters)
+         .find(|(_, outcome)| outcome.is_some())
+         .map(|(&parameter, _)| parameter)
+   }
+
+   // nothing we tried lost after something won
+   fn is_monotonic(&self) -> bool {
+      let first_winner = self.smallest_winner(self.parameters.clone());
+      let last_loser = self.results.iter()
+         .rev()
+         .find(|(_, outcome)| outcome.is_none())
+         .map(|(&parameter, _)| parameter);
+
+      match (first_winner, last_loser) {
+         (Some(winner), Some(loser)) => loser < winner,
+         _ => true,
+      }
+   }
+
+   fn threshold(&mut self, parameter: isize) -> Option<Threshold<T>> {
+      let monotonic = self.is_monotonic();
+      let evaluated = self.results.len();
+
+      self.results.remove(&parameter)
+         .flatten()
+         .map(|outcome| Threshold {
+            parameter,
+            outcome,
+            monotonic,
+            evaluated,
+         })
+   }
+
+   // each of these on its own thread, skipping anything we've already tried
+   fn evaluate(&mut self, parameters: &[isize]) {
+      let win = &self.win;
+      let results = &self.results;
+
+      let outcomes: Vec<(isize, Option<T>)> = thread::scope(|scope| {
+         let handles: Vec<_> = parameters.iter()
+            .copied()
+            .filter(|parameter| !results.contains_key(parameter))
+            .map(|parameter| scope.spawn(move || (parameter, win(parameter))))
+            .collect();
+
+         handles.into_iter()
+            .map(|handle| handle.join().expect("our parameter panicked"))
+            .collect()
+      });
+
+      self.results.extend(outcomes);
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use std::sync::atomic::{AtomicUsize, Ordering};
+
+   use super::*;
+
+   #[test]
+   fn monotonic() {
+      let threshold = min_winning(4..200, |parameter| if parameter >= 23 { Some(parameter * 10) } else { None }).unwrap();
+
+      assert_eq!((23, 230, true), (threshold.parameter, threshold.outcome, threshold.monotonic));
+   }
+
+   #[test]
+   fn starts_winning() {
+      let threshold = min_winning(4..200, Some).unwrap();
+
+      assert_eq!((4, true, 1), (threshold.parameter, threshold.monotonic, threshold.evaluated));
+   }
+
+   #[test]
+   fn winner_hidden_between_losers() {
+      let win = |parameter| if parameter == 30 || parameter >= 41 { Some(parameter) } else { None };
+      let threshold = min_winning(1..100_000, win).unwrap();
+
+      assert_eq!((30, false), (threshold.parameter, threshold.monotonic));
+   }
+
+   #[test]
+   fn winner_below_our_first_gallop() {
+      // we'll gallop past 3 before anything wins
+      let win = |parameter| if parameter == 3 || parameter >= 100 { Some(parameter) } else { None };
+      let threshold = min_winning(1..1000, win).unwrap();
+
+      assert_eq!((3, false), (threshold.parameter, threshold.monotonic));
+   }
+
+   #[test]
+   fn only_our_last_parameter_wins() {
+      let threshold = min_winning(0..50, |parameter| if parameter == 49 { Some(()) } else { None }).unwrap();
+
+      assert_eq!((49, true), (threshold.parameter, threshold.monotonic));
+   }
+
+   #[test]
+   fn never_wins() {
+      assert_eq!(None, min_winning(0..50, |_| None::<()>));
+      assert_eq!(None, min_winning(10..10, Some));
+   }
+
+   #[test]
+   fn tries_each_parameter_once() {
+      let calls = AtomicUsize::new(0);
+      let threshold = min_winning(1..100_000, |parameter| {
+         calls.fetch_add(1, Ordering::SeqCst);
+
+         if parameter == 30 || parameter >= 41 { Some(()) } else { None }
+      }).unwrap();
+
+      assert_eq!(calls.load(Ordering::SeqCst), threshold.evaluated);
+   }
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::process;
+
+use elfcode::*;
+use elfcode::debugger::Debugger;
+
+// cargo run --bin elfdb -- <program> [r0,r1,...]
+fn main() {
+   let args: Vec<String> = env::args().collect();
+   if args.len() < 2 {
+      eprintln!("usage: elfdb <program> [r0,r1,r2,r3,r4,r5]");
+      return;
+   }
+
+   let contents = fs::read_to_string(&args[1])
+      .unwrap_or_else(|e| {
+         eprintln!("couldn't read {}: {}", args[1], e);
+         process::exit(1);
+      });
+   let program = Program::parse(&contents)
+      .unwrap_or_else(|e| {
+         eprintln!("{}", e.in_file(&args[1]));
+         process::exit(1);
+      });
+
+   let registers: Vec<usize> = match args.get(2) {
+      Some(raw) => raw.split(',')
+         .map(|register| register.trim().parse::<usize>().ok())
+         .collect::<Option<Vec<usize>>>()
+         .filter(|registers| registers.len() == REGISTERS)
+         .unwrap_or_else(|| {
+            eprintln!("expected {} comma separated registers, got {}", REGISTERS, raw);
+            process::exit(1);
+         }),
+      None => vec![0; REGISTERS],
+   };
+
+   let mut debugger = Debugger::new(Machine::new(&program, registers));
+
+   let stdin = io::stdin();
+   debugger.repl(stdin.lock(), io::stdout()).unwrap();
+}
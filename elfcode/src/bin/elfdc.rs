@@ -0,0 +1,28 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use elfcode::*;
+use elfcode::decompiler::decompile;
+
+// cargo run --bin elfdc -- <program>
+fn main() {
+   let args: Vec<String> = env::args().collect();
+   if args.len() < 2 {
+      eprintln!("usage: elfdc <program>");
+      return;
+   }
+
+   let contents = fs::read_to_string(&args[1])
+      .unwrap_or_else(|e| {
+         eprintln!("couldn't read {}: {}", args[1], e);
+         process::exit(1);
+      });
+   let program = Program::parse(&contents)
+      .unwrap_or_else(|e| {
+         eprintln!("{}", e.in_file(&args[1]));
+         process::exit(1);
+      });
+
+   print!("{}", decompile(&program.instructions, program.ip_register));
+}
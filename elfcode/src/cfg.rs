@@ -0,0 +1,533 @@
+use std::collections::BTreeSet;
+
+use crate::expr::{BinOp, Expr};
+use crate::program::Instruction;
+
+/// A single non-jump instruction as `register = value`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Statement {
+   pub ip: usize,
+   pub register: usize,
+   pub value: Expr,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Terminator {
+   /// continue at the instruction index, anything outside the program halts
+   Jump(usize),
+   Branch {
+      condition: Expr,
+      taken: usize,
+      not_taken: usize,
+      /// set when we're relying on a register only ever holding 0 or 1 to treat this as a branch
+      assumes_boolean: Option<usize>,
+   },
+   /// a jump we can't resolve statically, the value is the instruction index we'll run next
+   Computed(Expr),
+}
+
+#[derive(Clone, Debug)]
+pub struct Block {
+   /// the instruction range [start, end) this block covers
+   pub start: usize,
+   pub end: usize,
+   pub statements: Vec<Statement>,
+   pub terminator: Terminator,
+}
+
+impl Block {
+   fn targets(&self) -> Vec<usize> {
+      match &self.terminator {
+         Terminator::Jump(target) => vec![*target],
+         Terminator::Branch { taken, not_taken, .. } => vec![*taken, *not_taken],
+         Terminator::Computed(_) => vec![],
+      }
+   }
+
+   fn reads(&self) -> Vec<usize> {
+      let mut reads = vec![];
+      match &self.terminator {
+         Terminator::Branch { condition, .. } => condition.registers(&mut reads),
+         Terminator::Computed(target) => target.registers(&mut reads),
+         Terminator::Jump(_) => {}
+      }
+
+      reads
+   }
+}
+
+/// The control flow graph of a program whose jumps are writes to its `#ip` register
+pub struct Cfg {
+   pub blocks: Vec<Block>,
+   /// successors by block index, `exit()` stands in for halting
+   pub successors: Vec<Vec<usize>>,
+   pub predecessors: Vec<Vec<usize>>,
+   pub ip_register: Option<usize>,
+   /// one more than the highest register the program touches
+   pub registers: usize,
+   block_at: Vec<Option<usize>>,
+}
+
+impl Cfg {
+   pub fn new(instructions: &[Instruction], ip_register: Option<usize>) -> Cfg {
+      Cfg::with_leaders(instructions, ip_register, &BTreeSet::new())
+   }
+
+   /// Like `new` but also starting a block at each of our `leaders`
+   pub fn with_leaders(instructions: &[Instruction], ip_register: Option<usize>, leaders: &BTreeSet<usize>) -> Cfg {
+      let registers = instructions.iter()
+         .flat_map(|instruction| {
+            let mut used = vec![instruction.c];
+            if instruction.op_code.reads_a() {
+               used.push(instruction.a);
+            }
+            if instruction.op_code.reads_b() {
+               used.push(instruction.b);
+            }
+            used
+         })
+         .chain(ip_register)
+         .max()
+         .map(|max| max + 1)
+         .unwrap_or(0);
+
+      let jumps: Vec<Option<Terminator>> = instructions.iter().enumerate()
+         .map(|(ip, instruction)| {
+            if Some(instruction.c) == ip_register {
+               Some(jump_for(instructions, ip_register, ip))
+            } else {
+               None
+            }
+         })
+         .collect();
+
+      // every jump target and everything following a jump starts a new block
+      let mut leaders = leaders.clone();
+      leaders.insert(0);
+      for (ip, jump) in jumps.iter().enumerate() {
+         if let Some(jump) = jump {
+            leaders.insert(ip + 1);
+            match jump {
+               Terminator::Jump(target) => {
+                  leaders.insert(*target);
+               }
+               Terminator::Branch { taken, not_taken, .. } => {
+                  leaders.insert(*taken);
+                  leaders.insert(*not_taken);
+               }
+               Terminator::Computed(_) => {}
+            }
+         }
+      }
+      let leaders: Vec<usize> = leaders.into_iter()
+         .filter(|leader| *leader < instructions.len())
+         .collect();
+
+      let mut blocks = vec![];
+      let mut block_at = vec![None; instructions.len()];
+      for (i, start) in leaders.iter().enumerate() {
+         let limit = leaders.get(i + 1).cloned().unwrap_or(instructions.len());
+
+         let mut statements = vec![];
+         let mut terminator = Terminator::Jump(limit);
+         let mut end = limit;
+         for ip in *start..limit {
+            if let Some(jump) = &jumps[ip] {
+               terminator = jump.clone();
+               end = ip + 1;
+               break;
+            }
+
+            statements.push(Statement {
+               ip,
+               register: instructions[ip].c,
+               value: Expr::for_instruction(&instructions[ip], ip_register, ip),
+            });
+         }
+
+         block_at[*start] = Some(blocks.len());
+         blocks.push(Block {
+            start: *start,
+            end,
+            statements,
+            terminator,
+         });
+      }
+
+      let mut cfg = Cfg {
+         blocks,
+         successors: vec![],
+         predecessors: vec![],
+         ip_register,
+         registers,
+         block_at,
+      };
+      cfg.link();
+
+      cfg
+   }
+
+   /// The virtual block every halting path flows into
+   pub fn exit(&self) -> usize {
+      self.blocks.len()
+   }
+
+   /// Which block an instruction index starts, `exit()` for anything outside the program
+   pub fn block_for(&self, ip: usize) -> usize {
+      self.block_at.get(ip)
+         .cloned()
+         .unwrap_or(None)
+         .unwrap_or(self.exit())
+   }
+
+   fn link(&mut self) {
+      self.successors = self.blocks.iter()
+         .map(|block| {
+            let mut successors: Vec<usize> = block.targets().into_iter()
+               .map(|target| self.block_for(target))
+               .collect();
+            if successors.is_empty() {
+               successors.push(self.exit());
+            }
+            successors.dedup();
+
+            successors
+         })
+         .collect();
+
+      self.predecessors = vec![vec![]; self.blocks.len() + 1];
+      for (from, successors) in self.successors.iter().enumerate() {
+         for to in successors {
+            self.predecessors[*to].push(from);
+         }
+      }
+   }
+
+   /// Which registers are read before being written on some path out of each block
+   pub fn live_out(&self) -> Vec<BTreeSet<usize>> {
+      // the puzzles only ever ask for register 0 once we halt
+      let halting: BTreeSet<usize> = (0..self.registers.min(1))
+         .filter(|r| Some(*r) != self.ip_register)
+         .collect();
+      // a computed jump could land on any block, so every register might still be read after one
+      let anywhere: BTreeSet<usize> = (0..self.registers)
+         .filter(|r| Some(*r) != self.ip_register)
+         .collect();
+
+      let (uses, defs): (Vec<BTreeSet<usize>>, Vec<BTreeSet<usize>>) = self.blocks.iter()
+         .map(|block| {
+            let mut uses = BTreeSet::new();
+            let mut defs = BTreeSet::new();
+            for statement in block.statements.iter() {
+               let mut reads = vec![];
+               statement.value.registers(&mut reads);
+               uses.extend(reads.into_iter().filter(|r| !defs.contains(r)));
+               defs.insert(statement.register);
+            }
+            uses.extend(block.reads().into_iter().filter(|r| !defs.contains(r)));
+
+            (uses, defs)
+         })
+         .unzip();
+
+      let mut live_in: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); self.blocks.len()];
+      let mut live_out: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); self.blocks.len()];
+
+      let mut changed = true;
+      while changed {
+         changed = false;
+         for b in (0..self.blocks.len()).rev() {
+            let mut out = BTreeSet::new();
+            if let Terminator::Computed(_) = self.blocks[b].terminator {
+               out.extend(anywhere.iter().cloned());
+            }
+            for successor in self.successors[b].iter() {
+               if *successor == self.exit() {
+                  out.extend(halting.iter().cloned());
+               } else {
+                  out.extend(live_in[*successor].iter().cloned());
+               }
+            }
+
+            let mut next_in: BTreeSet<usize> = out.difference(&defs[b]).cloned().collect();
+            next_in.extend(uses[b].iter().cloned());
+
+            if next_in != live_in[b] || out != live_out[b] {
+               live_in[b] = next_in;
+               live_out[b] = out;
+               changed = true;
+            }
+         }
+      }
+
+      live_out
+   }
+
+   /// Folds single use temporaries into where they're used and drops stores nothing reads.
+   /// Gives back any computed jump we resolved into the middle of a block, which has to start a block of its own
+   /// with `with_leaders` before we can trust our successors
+   pub fn propagate(&mut self) -> BTreeSet<usize> {
+      let live_out = self.live_out();
+
+      for (b, block) in self.blocks.iter_mut().enumerate() {
+         while propagate_once(block, &live_out[b]) {}
+
+         // we have to keep everything a computed jump might need, but any constants it reads can still tell us where it goes
+         if let Terminator::Computed(target) = &block.terminator {
+            let mut reads = vec![];
+            target.registers(&mut reads);
+
+            let target = reads.into_iter().fold(target.clone(), |target, register| {
+               match block.statements.iter().rev().find(|statement| statement.register == register) {
+                  Some(Statement { value: value @ Expr::Const(_), .. }) => target.substitute(register, value),
+                  _ => target,
+               }
+            });
+            block.terminator = Terminator::Computed(target);
+         }
+
+         // a branch on a constant is really just a jump
+         let resolved = match &block.terminator {
+            Terminator::Branch { condition: Expr::Const(value), taken, not_taken, .. } => {
+               Some(Terminator::Jump(if *value != 0 { *taken } else { *not_taken }))
+            }
+            Terminator::Computed(Expr::Const(target)) => Some(Terminator::Jump(*target)),
+            _ => None,
+         };
+         if let Some(terminator) = resolved {
+            block.terminator = terminator;
+         }
+      }
+
+      self.link();
+
+      self.blocks.iter()
+         .filter_map(|block| match block.terminator {
+            Terminator::Jump(target) if target < self.block_at.len() && self.block_at[target].is_none() => Some(target),
+            _ => None,
+         })
+         .collect()
+   }
+
+   /// The blocks we can get to from the entry block
+   pub fn reachable(&self) -> Vec<bool> {
+      let mut reachable = vec![false; self.blocks.len() + 1];
+      let mut stack = vec![0];
+      while let Some(b) = stack.pop() {
+         if b < self.blocks.len() && !reachable[b] {
+            reachable[b] = true;
+            stack.extend(self.successors[b].iter().cloned());
+         } else if b == self.exit() {
+            reachable[b] = true;
+         }
+      }
+
+      reachable
+   }
+
+   /// For each block the set of blocks that must be passed through to reach it from the entry
+   pub fn dominators(&self) -> Vec<BTreeSet<usize>> {
+      let reachable = self.reachable();
+      let all: BTreeSet<usize> = (0..=self.blocks.len()).filter(|b| reachable[*b]).collect();
+
+      let mut dominators: Vec<BTreeSet<usize>> = (0..=self.blocks.len())
+         .map(|b| if b == 0 { vec![0].into_iter().collect() } else { all.clone() })
+         .collect();
+
+      let mut changed = true;
+      while changed {
+         changed = false;
+         for b in 1..=self.blocks.len() {
+            if !reachable[b] {
+               continue;
+            }
+
+            let mut next = intersect(self.predecessors[b].iter()
+               .filter(|p| reachable[**p])
+               .map(|p| &dominators[*p]));
+            next.insert(b);
+
+            if next != dominators[b] {
+               dominators[b] = next;
+               changed = true;
+            }
+         }
+      }
+
+      dominators
+   }
+
+   /// For each block the closest block every path from it to halting must pass through
+   pub fn immediate_post_dominators(&self) -> Vec<Option<usize>> {
+      let exit = self.exit();
+
+      // only blocks that can halt have post dominators
+      let mut reaches_exit = vec![false; self.blocks.len() + 1];
+      reaches_exit[exit] = true;
+      let mut stack = vec![exit];
+      while let Some(b) = stack.pop() {
+         for p in self.predecessors[b].iter() {
+            if !reaches_exit[*p] {
+               reaches_exit[*p] = true;
+               stack.push(*p);
+            }
+         }
+      }
+
+      let all: BTreeSet<usize> = (0..=exit).filter(|b| reaches_exit[*b]).collect();
+      let mut post_dominators: Vec<BTreeSet<usize>> = (0..=exit)
+         .map(|b| if b == exit { vec![exit].into_iter().collect() } else { all.clone() })
+         .collect();
+
+      let mut changed = true;
+      while changed {
+         changed = false;
+         for b in (0..self.blocks.len()).rev() {
+            if !reaches_exit[b] {
+               continue;
+            }
+
+            let mut next = intersect(self.successors[b].iter()
+               .filter(|s| reaches_exit[**s])
+               .map(|s| &post_dominators[*s]));
+            next.insert(b);
+
+            if next != post_dominators[b] {
+               post_dominators[b] = next;
+               changed = true;
+            }
+         }
+      }
+
+      (0..=exit)
+         .map(|b| {
+            if !reaches_exit[b] || b == exit {
+               return None;
+            }
+
+            // our closest post dominator is the one that is itself post dominated by all the others
+            post_dominators[b].iter()
+               .filter(|d| **d != b)
+               .find(|d| post_dominators[**d].len() == post_dominators[b].len() - 1)
+               .cloned()
+         })
+         .collect()
+   }
+}
+
+fn intersect<'a, I>(mut sets: I) -> BTreeSet<usize> where
+   I: Iterator<Item=&'a BTreeSet<usize>> {
+   let first = match sets.next() {
+      Some(first) => first.clone(),
+      None => return BTreeSet::new(),
+   };
+
+   sets.fold(first, |result, set| result.intersection(set).cloned().collect())
+}
+
+/// Works out where an instruction that writes the ip register sends us
+fn jump_for(instructions: &[Instruction], ip_register: Option<usize>, ip: usize) -> Terminator {
+   let value = Expr::for_instruction(&instructions[ip], ip_register, ip);
+
+   if let Some(target) = value.as_const() {
+      return Terminator::Jump(target + 1);
+   }
+
+   // `ip += r` is a branch over the next instruction when r is a 0/1 flag
+   let offset = match &value {
+      Expr::Bin(BinOp::Add, left, right) => match (left.as_ref(), right.as_ref()) {
+         (Expr::Const(base), Expr::Reg(r)) if *base == ip => Some(*r),
+         (Expr::Reg(r), Expr::Const(base)) if *base == ip => Some(*r),
+         _ => None,
+      }
+      _ => None,
+   };
+
+   match offset {
+      Some(r) => {
+         let is_flag = ip > 0 && instructions[ip - 1].c == r &&
+            Expr::for_instruction(&instructions[ip - 1], ip_register, ip - 1).is_comparison();
+
+         Terminator::Branch {
+            condition: Expr::Reg(r).truthy(),
+            taken: ip + 2,
+            not_taken: ip + 1,
+            assumes_boolean: if is_flag { None } else { Some(r) },
+         }
+      }
+      None => Terminator::Computed(Expr::bin(BinOp::Add, value, Expr::Const(1))),
+   }
+}
+
+fn propagate_once(block: &mut Block, live_out: &BTreeSet<usize>) -> bool {
+   for j in 0..block.statements.len() {
+      let register = block.statements[j].register;
+      let value = block.statements[j].value.clone();
+
+      // find everywhere our value is read before it's overwritten
+      let mut uses = vec![];
+      let mut overwritten = false;
+      for k in (j + 1)..block.statements.len() {
+         let reads = block.statements[k].value.count_reads(register);
+         if reads > 0 {
+            uses.push((Some(k), reads));
+         }
+         if block.statements[k].register == register {
+            overwritten = true;
+            break;
+         }
+      }
+      if !overwritten {
+         let reads = block.reads().iter().filter(|r| **r == register).count();
+         if reads > 0 {
+            uses.push((None, reads));
+         }
+         if live_out.contains(&register) {
+            continue;
+         }
+      }
+
+      let total: usize = uses.iter().map(|(_, reads)| reads).sum();
+      if total == 0 {
+         block.statements.remove(j);
+         return true;
+      }
+      if total > 1 {
+         continue;
+      }
+
+      // make sure nothing our value depends on changes before we get to the use
+      let (use_at, _) = uses[0];
+      let until = use_at.unwrap_or(block.statements.len());
+      let mut depends_on = vec![];
+      value.registers(&mut depends_on);
+      let clobbered = block.statements[(j + 1)..until].iter()
+         .any(|statement| depends_on.contains(&statement.register));
+      if clobbered {
+         continue;
+      }
+
+      match use_at {
+         Some(k) => {
+            block.statements[k].value = block.statements[k].value.substitute(register, &value);
+         }
+         None => {
+            block.terminator = match &block.terminator {
+               Terminator::Branch { condition, taken, not_taken, assumes_boolean } => Terminator::Branch {
+                  condition: condition.substitute(register, &value).truthy(),
+                  taken: *taken,
+                  not_taken: *not_taken,
+                  assumes_boolean: *assumes_boolean,
+               },
+               Terminator::Computed(target) => Terminator::Computed(target.substitute(register, &value)),
+               Terminator::Jump(target) => Terminator::Jump(*target),
+            };
+         }
+      }
+      block.statements.remove(j);
+
+      return true;
+   }
+
+   false
+}
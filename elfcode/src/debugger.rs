@@ -0,0 +1,406 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::io;
+
+use crate::machine::Machine;
+
+pub enum Watch {
+   /// stop whenever the register holds a different value after a step
+   Changed,
+   /// stop whenever an instruction writes a value into the register that passes our predicate
+   Matches(Box<dyn Fn(usize) -> bool>),
+}
+
+pub struct Watchpoint {
+   pub register: usize,
+   pub watch: Watch,
+   /// how this watchpoint was described to us, used when listing them
+   pub description: String,
+}
+
+impl Watchpoint {
+   pub fn changed(register: usize) -> Watchpoint {
+      Watchpoint {
+         register,
+         watch: Watch::Changed,
+         description: format!("r{} changed", register),
+      }
+   }
+
+   pub fn matches<P>(register: usize, description: &str, predicate: P) -> Watchpoint where
+      P: Fn(usize) -> bool + 'static {
+      Watchpoint {
+         register,
+         watch: Watch::Matches(Box::new(predicate)),
+         description: format!("r{} {}", register, description),
+      }
+   }
+
+   fn triggered(&self, before: usize, after: usize, written: bool) -> bool {
+      match &self.watch {
+         Watch::Changed => before != after,
+         Watch::Matches(predicate) => written && predicate(after),
+      }
+   }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum StopReason {
+   Halted,
+   Breakpoint(usize),
+   /// the index of the watchpoint that fired along with the before and after value of its register
+   Watchpoint(usize, usize, usize),
+   Limit(u64),
+}
+
+impl fmt::Display for StopReason {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         StopReason::Halted => write!(f, "halted"),
+         StopReason::Breakpoint(ip) => write!(f, "breakpoint at {}", ip),
+         StopReason::Watchpoint(index, before, after) => write!(f, "watchpoint {}: {} -> {}", index, before, after),
+         StopReason::Limit(limit) => write!(f, "instruction limit of {} reached", limit),
+      }
+   }
+}
+
+pub struct Debugger<'a> {
+   machine: Machine<'a>,
+   breakpoints: BTreeSet<usize>,
+   watchpoints: Vec<Watchpoint>,
+   limit: Option<u64>,
+}
+
+impl<'a> Debugger<'a> {
+   pub fn new(machine: Machine<'a>) -> Debugger<'a> {
+      Debugger {
+         machine,
+         breakpoints: BTreeSet::new(),
+         watchpoints: vec![],
+         limit: None,
+      }
+   }
+
+   pub fn machine(&self) -> &Machine<'a> {
+      &self.machine
+   }
+
+   pub fn machine_mut(&mut self) -> &mut Machine<'a> {
+      &mut self.machine
+   }
+
+   pub fn add_breakpoint(&mut self, ip: usize) -> bool {
+      self.breakpoints.insert(ip)
+   }
+
+   pub fn remove_breakpoint(&mut self, ip: usize) -> bool {
+      self.breakpoints.remove(&ip)
+   }
+
+   /// The index of our new watchpoint, nothing if our machine doesn't have its register
+   pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) -> Option<usize> {
+      if watchpoint.register >= self.machine.registers().len() {
+         return None;
+      }
+
+      self.watchpoints.push(watchpoint);
+
+      Some(self.watchpoints.len() - 1)
+   }
+
+   pub fn remove_watchpoint(&mut self, index: usize) -> Option<Watchpoint> {
+      if index < self.watchpoints.len() {
+         Some(self.watchpoints.remove(index))
+      } else {
+         None
+      }
+   }
+
+   /// Stop once the machine has executed this many instructions in total
+   pub fn set_limit(&mut self, limit: Option<u64>) {
+      self.limit = limit;
+   }
+
+   /// Runs a single instruction, reporting any watchpoint it trips
+   pub fn step(&mut self) -> Option<StopReason> {
+      if let Some(limit) = self.limit {
+         if self.machine.executed() >= limit {
+            return Some(StopReason::Limit(limit));
+         }
+      }
+
+      let written = match self.machine.current() {
+         Some(instruction) => instruction.c,
+         None => return Some(StopReason::Halted),
+      };
+
+      let before = self.machine.registers().to_vec();
+      self.machine.step();
+      let after = self.machine.registers();
+
+      for (i, watchpoint) in self.watchpoints.iter().enumerate() {
+         let register = watchpoint.register;
+         if watchpoint.triggered(before[register], after[register], written == register) {
+            return Some(StopReason::Watchpoint(i, before[register], after[register]));
+         }
+      }
+
+      if self.machine.halted() {
+         Some(StopReason::Halted)
+      } else {
+         None
+      }
+   }
+
+   /// Runs until we halt or hit a breakpoint, watchpoint or our instruction limit
+   pub fn resume(&mut self) -> StopReason {
+      // always make progress, even if we're sitting on a breakpoint
+      if let Some(reason) = self.step() {
+         return reason;
+      }
+
+      loop {
+         let ip = self.machine.ip();
+         if self.breakpoints.contains(&ip) {
+            return StopReason::Breakpoint(ip);
+         }
+
+         if let Some(reason) = self.step() {
+            return reason;
+         }
+      }
+   }
+
+   pub fn dump_registers(&self) -> String {
+      let registers: Vec<String> = self.machine.registers().iter().enumerate()
+         .map(|(i, value)| format!("r{}={}", i, value))
+         .collect();
+
+      format!("ip={} executed={} {}", self.machine.ip(), self.machine.executed(), registers.join(" "))
+   }
+
+   fn print_current<W: Write>(&self, output: &mut W) -> io::Result<()> {
+      match self.machine.current() {
+         Some(instruction) => writeln!(output, "{:02}: {}", self.machine.ip(), instruction),
+         None => writeln!(output, "{:02}: <halted>", self.machine.ip()),
+      }
+   }
+
+   fn print_listing<W: Write>(&self, output: &mut W) -> io::Result<()> {
+      for (i, instruction) in self.machine.program().instructions.iter().enumerate() {
+         let marker = if i == self.machine.ip() { ">" } else { " " };
+         let breakpoint = if self.breakpoints.contains(&i) { "*" } else { " " };
+
+         writeln!(output, "{}{}{:02}: {}", marker, breakpoint, i, instruction)?;
+      }
+
+      Ok(())
+   }
+
+   fn print_info<W: Write>(&self, output: &mut W) -> io::Result<()> {
+      let breakpoints: Vec<String> = self.breakpoints.iter().map(|ip| ip.to_string()).collect();
+      writeln!(output, "breakpoints: {}", breakpoints.join(", "))?;
+
+      for (i, watchpoint) in self.watchpoints.iter().enumerate() {
+         writeln!(output, "watchpoint {}: {}", i, watchpoint.description)?;
+      }
+
+      match self.limit {
+         Some(limit) => writeln!(output, "limit: {}", limit),
+         None => writeln!(output, "limit: none"),
+      }
+   }
+
+   /// Reads commands from our input until it's exhausted or we're told to quit
+   pub fn repl<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+      write!(output, "(elfdb) ")?;
+      output.flush()?;
+
+      for line in input.lines() {
+         let line = line?;
+         let args: Vec<&str> = line.split_whitespace().collect();
+
+         if let Some(command) = args.first() {
+            match Command::parse(command, &args[1..]) {
+               Ok(Command::Quit) => return Ok(()),
+               Ok(command) => self.execute(command, &mut output)?,
+               Err(message) => writeln!(output, "{}", message)?,
+            }
+         }
+
+         write!(output, "(elfdb) ")?;
+         output.flush()?;
+      }
+
+      writeln!(output)
+   }
+
+   fn execute<W: Write>(&mut self, command: Command, output: &mut W) -> io::Result<()> {
+      match command {
+         Command::Step(count) => {
+            for _ in 0..count {
+               if let Some(reason) = self.step() {
+                  writeln!(output, "stopped: {}", reason)?;
+                  break;
+               }
+            }
+            self.print_current(output)?;
+         }
+         Command::Continue => {
+            let reason = self.resume();
+            writeln!(output, "stopped: {}", reason)?;
+            self.print_current(output)?;
+         }
+         Command::Break(ip) => {
+            self.add_breakpoint(ip);
+            writeln!(output, "breakpoint at {}", ip)?;
+         }
+         Command::Delete(ip) => {
+            if !self.remove_breakpoint(ip) {
+               writeln!(output, "no breakpoint at {}", ip)?;
+            }
+         }
+         Command::Watch(watchpoint) => {
+            let (register, description) = (watchpoint.register, watchpoint.description.clone());
+            match self.add_watchpoint(watchpoint) {
+               Some(index) => writeln!(output, "watchpoint {}: {}", index, description)?,
+               None => writeln!(output, "no register r{}", register)?,
+            }
+         }
+         Command::Unwatch(index) => {
+            if self.remove_watchpoint(index).is_none() {
+               writeln!(output, "no watchpoint {}", index)?;
+            }
+         }
+         Command::Limit(limit) => {
+            self.set_limit(limit);
+         }
+         Command::Set(register, value) => {
+            match self.machine.registers_mut().get_mut(register) {
+               Some(current) => *current = value,
+               None => writeln!(output, "no register r{}", register)?,
+            }
+
+            // our machine writes its ip into the bound register before every instruction, so move the ip too
+            if Some(register) == self.machine.program().ip_register {
+               self.machine.set_ip(value);
+            }
+         }
+         Command::Registers => {
+            writeln!(output, "{}", self.dump_registers())?;
+         }
+         Command::List => {
+            self.print_listing(output)?;
+         }
+         Command::Info => {
+            self.print_info(output)?;
+         }
+         Command::Help => {
+            writeln!(output, "{}", HELP)?;
+         }
+         Command::Quit => {}
+      }
+
+      Ok(())
+   }
+}
+
+const HELP: &str = "\
+s, step [n]              run n instructions (default 1)
+c, continue              run until a breakpoint, watchpoint, limit or halt
+b, break <ip>            break before the instruction at <ip>
+d, delete <ip>           remove the breakpoint at <ip>
+w, watch r<n>            stop when r<n> changes
+w, watch r<n> <op> <v>   stop when r<n> is written with a value where `value <op> v`, op is one of == != < <= > >=
+unwatch <i>              remove watchpoint <i>
+limit <n>|off            stop after n instructions in total
+set r<n> <v>             overwrite a register, jumping to <v> if it's bound to the ip
+r, regs                  dump the registers
+l, list                  print the program
+i, info                  list breakpoints, watchpoints and the limit
+q, quit                  exit";
+
+enum Command {
+   Step(usize),
+   Continue,
+   Break(usize),
+   Delete(usize),
+   Watch(Watchpoint),
+   Unwatch(usize),
+   Limit(Option<u64>),
+   Set(usize, usize),
+   Registers,
+   List,
+   Info,
+   Help,
+   Quit,
+}
+
+impl Command {
+   fn parse(command: &str, args: &[&str]) -> Result<Command, String> {
+      let number = |i: usize| -> Result<usize, String> {
+         args.get(i)
+            .ok_or(format!("{} is missing an argument", command))
+            .and_then(|arg| arg.parse::<usize>().map_err(|_| format!("not a number: {}", arg)))
+      };
+
+      let register = |i: usize| -> Result<usize, String> {
+         args.get(i)
+            .ok_or(format!("{} is missing a register", command))
+            .and_then(|arg| {
+               arg.trim_start_matches('r').parse::<usize>()
+                  .map_err(|_| format!("not a register: {}", arg))
+            })
+      };
+
+      match command {
+         "s" | "step" => {
+            if args.is_empty() {
+               Ok(Command::Step(1))
+            } else {
+               number(0).map(Command::Step)
+            }
+         }
+         "c" | "continue" => Ok(Command::Continue),
+         "b" | "break" => number(0).map(Command::Break),
+         "d" | "delete" => number(0).map(Command::Delete),
+         "w" | "watch" => {
+            let register = register(0)?;
+
+            if args.len() == 1 {
+               Ok(Command::Watch(Watchpoint::changed(register)))
+            } else {
+               let value = number(2)?;
+               let description = format!("{} {}", args[1], value);
+               let watchpoint = match args[1] {
+                  "==" => Watchpoint::matches(register, &description, move |v| v == value),
+                  "!=" => Watchpoint::matches(register, &description, move |v| v != value),
+                  "<" => Watchpoint::matches(register, &description, move |v| v < value),
+                  "<=" => Watchpoint::matches(register, &description, move |v| v <= value),
+                  ">" => Watchpoint::matches(register, &description, move |v| v > value),
+                  ">=" => Watchpoint::matches(register, &description, move |v| v >= value),
+                  op => return Err(format!("unknown comparison: {}", op)),
+               };
+
+               Ok(Command::Watch(watchpoint))
+            }
+         }
+         "unwatch" => number(0).map(Command::Unwatch),
+         "limit" => {
+            if args.first() == Some(&"off") {
+               Ok(Command::Limit(None))
+            } else {
+               number(0).map(|limit| Command::Limit(Some(limit as u64)))
+            }
+         }
+         "set" => Ok(Command::Set(register(0)?, number(1)?)),
+         "r" | "regs" => Ok(Command::Registers),
+         "l" | "list" => Ok(Command::List),
+         "i" | "info" => Ok(Command::Info),
+         "h" | "help" => Ok(Command::Help),
+         "q" | "quit" => Ok(Command::Quit),
+         _ => Err(format!("unknown command: {}, try help", command)),
+      }
+   }
+}
@@ -0,0 +1,497 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use crate::cfg::{Cfg, Terminator};
+use crate::expr::{BinOp, Expr};
+use crate::program::Instruction;
+
+/// Structured pseudo-Rust, loops are labelled by the instruction index of their header
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Stmt {
+   Assign(usize, Expr),
+   If(Expr, Vec<Stmt>, Vec<Stmt>),
+   Loop(usize, Vec<Stmt>),
+   While(usize, Expr, Vec<Stmt>),
+   Break(usize),
+   Continue(usize),
+   Return,
+   /// jump to the block starting at this instruction index, only used when we can't structure something
+   Goto(usize),
+   Label(usize),
+   ComputedGoto(Expr),
+   Comment(String),
+}
+
+impl Stmt {
+   /// Does control never fall out the bottom of this statement
+   fn diverges(&self) -> bool {
+      match self {
+         Stmt::Break(_) | Stmt::Continue(_) | Stmt::Return | Stmt::Goto(_) | Stmt::ComputedGoto(_) => true,
+         Stmt::If(_, then, otherwise) => ends_diverging(then) && ends_diverging(otherwise),
+         _ => false,
+      }
+   }
+}
+
+fn ends_diverging(stmts: &[Stmt]) -> bool {
+   stmts.last().map(|stmt| stmt.diverges()).unwrap_or(false)
+}
+
+pub struct Decompiled {
+   pub body: Vec<Stmt>,
+   pub ip_register: Option<usize>,
+   /// every register other than the ip register, these are our function's parameters
+   pub registers: Vec<usize>,
+   /// instruction ranges [start, end) nothing can jump to
+   pub unreachable: Vec<(usize, usize)>,
+   instructions: usize,
+}
+
+/// Rebuilds loops and if/else from the writes an `#ip` program makes to its instruction pointer
+pub fn decompile(instructions: &[Instruction], ip_register: Option<usize>) -> Decompiled {
+   // a computed jump we can resolve might land in the middle of a block, so split there and start again
+   let mut leaders = BTreeSet::new();
+   let cfg = loop {
+      let mut cfg = Cfg::with_leaders(instructions, ip_register, &leaders);
+      let stranded = cfg.propagate();
+      if stranded.is_empty() {
+         break cfg;
+      }
+
+      leaders.extend(stranded);
+   };
+
+   let mut structurer = Structurer::new(&cfg);
+   let mut body = vec![];
+   if !cfg.blocks.is_empty() {
+      structurer.emit_sequence(0, None, &mut vec![], &mut body);
+   } else {
+      body.push(Stmt::Return);
+   }
+
+   let gotos = structurer.gotos;
+   let mut body = simplify(body, &gotos);
+   // falling off the end already halts
+   if body.last() == Some(&Stmt::Return) {
+      body.pop();
+   }
+
+   let reachable = cfg.reachable();
+   let unreachable = cfg.blocks.iter().enumerate()
+      .filter(|(b, _)| !reachable[*b])
+      .map(|(_, block)| (block.start, block.end))
+      .collect();
+
+   Decompiled {
+      body,
+      ip_register,
+      registers: (0..cfg.registers).filter(|r| Some(*r) != ip_register).collect(),
+      unreachable,
+      instructions: instructions.len(),
+   }
+}
+
+struct Loop {
+   body: BTreeSet<usize>,
+   follow: Option<usize>,
+}
+
+struct Structurer<'a> {
+   cfg: &'a Cfg,
+   post_dominators: Vec<Option<usize>>,
+   loops: HashMap<usize, Loop>,
+   emitted: Vec<bool>,
+   /// blocks something jumps to with a goto, by instruction index
+   gotos: BTreeSet<usize>,
+}
+
+impl<'a> Structurer<'a> {
+   fn new(cfg: &'a Cfg) -> Structurer<'a> {
+      let post_dominators = cfg.immediate_post_dominators();
+      let loops = find_loops(cfg, &post_dominators);
+
+      Structurer {
+         cfg,
+         post_dominators,
+         loops,
+         emitted: vec![false; cfg.blocks.len()],
+         gotos: BTreeSet::new(),
+      }
+   }
+
+   fn start(&self, b: usize) -> usize {
+      self.cfg.blocks[b].start
+   }
+
+   /// If reaching this block leaves the code we're emitting, how do we get there
+   fn leave(&self, b: usize, loops: &[usize]) -> Option<Stmt> {
+      if b == self.cfg.exit() {
+         return Some(Stmt::Return);
+      }
+
+      for header in loops.iter().rev() {
+         if b == *header {
+            return Some(Stmt::Continue(self.start(*header)));
+         }
+         if self.loops[header].follow == Some(b) {
+            return Some(Stmt::Break(self.start(*header)));
+         }
+      }
+
+      None
+   }
+
+   fn emit_sequence(&mut self, start: usize, until: Option<usize>, loops: &mut Vec<usize>, out: &mut Vec<Stmt>) {
+      let mut current = start;
+      loop {
+         if Some(current) == until {
+            return;
+         }
+         if let Some(stmt) = self.leave(current, loops) {
+            out.push(stmt);
+            return;
+         }
+         if self.emitted[current] {
+            self.gotos.insert(self.start(current));
+            out.push(Stmt::Goto(self.start(current)));
+            return;
+         }
+
+         if self.loops.contains_key(&current) {
+            let mut body = vec![];
+            loops.push(current);
+            if let Some(next) = self.emit_block(current, loops, &mut body) {
+               self.emit_sequence(next, None, loops, &mut body);
+            }
+            loops.pop();
+            out.push(Stmt::Loop(self.start(current), body));
+
+            match self.loops[&current].follow {
+               Some(follow) => current = follow,
+               None => return,
+            }
+         } else {
+            match self.emit_block(current, loops, out) {
+               Some(next) => current = next,
+               None => return,
+            }
+         }
+      }
+   }
+
+   /// Emits a block and any if/else it ends in, returning where we continue afterwards
+   fn emit_block(&mut self, b: usize, loops: &mut Vec<usize>, out: &mut Vec<Stmt>) -> Option<usize> {
+      self.emitted[b] = true;
+      let block = &self.cfg.blocks[b];
+
+      out.push(Stmt::Label(block.start));
+      for statement in block.statements.iter() {
+         out.push(Stmt::Assign(statement.register, statement.value.clone()));
+      }
+
+      match &block.terminator {
+         Terminator::Jump(target) => Some(self.cfg.block_for(*target)),
+         Terminator::Computed(target) => {
+            out.push(Stmt::ComputedGoto(target.clone()));
+            None
+         }
+         Terminator::Branch { condition, taken, not_taken, assumes_boolean } => {
+            if let Some(r) = assumes_boolean {
+               out.push(Stmt::Comment(format!("assumes r{} is always 0 or 1", r)));
+            }
+
+            let taken = self.cfg.block_for(*taken);
+            let not_taken = self.cfg.block_for(*not_taken);
+            let condition = condition.clone();
+
+            // where both sides meet up again, as long as that's still inside our loop
+            let mut merge = self.post_dominators[b];
+            if let (Some(header), Some(m)) = (loops.last(), merge) {
+               if !self.loops[header].body.contains(&m) {
+                  merge = None;
+               }
+            }
+
+            let mut then = vec![];
+            let mut otherwise = vec![];
+            if merge == Some(taken) {
+               self.emit_sequence(not_taken, merge, loops, &mut otherwise);
+            } else if merge == Some(not_taken) {
+               self.emit_sequence(taken, merge, loops, &mut then);
+            } else {
+               self.emit_sequence(taken, merge, loops, &mut then);
+               self.emit_sequence(not_taken, merge, loops, &mut otherwise);
+            }
+            out.push(Stmt::If(condition, then, otherwise));
+
+            merge
+         }
+      }
+   }
+}
+
+fn find_loops(cfg: &Cfg, post_dominators: &[Option<usize>]) -> HashMap<usize, Loop> {
+   let dominators = cfg.dominators();
+   let reachable = cfg.reachable();
+
+   let mut bodies: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+   for from in (0..cfg.blocks.len()).filter(|b| reachable[*b]) {
+      for header in cfg.successors[from].iter() {
+         // a back edge jumps to a block that dominates us
+         if *header != cfg.exit() && dominators[from].contains(header) {
+            let body = bodies.entry(*header).or_insert_with(|| vec![*header].into_iter().collect());
+
+            let mut stack = vec![from];
+            while let Some(b) = stack.pop() {
+               if body.insert(b) {
+                  stack.extend(cfg.predecessors[b].iter().filter(|p| reachable[**p]));
+               }
+            }
+         }
+      }
+   }
+
+   bodies.into_iter()
+      .map(|(header, body)| {
+         let exits: BTreeSet<usize> = body.iter()
+            .flat_map(|b| cfg.successors[*b].iter())
+            .filter(|s| !body.contains(s) && **s != cfg.exit())
+            .cloned()
+            .collect();
+
+         // prefer where the whole loop flows to, otherwise the first exit
+         let follow = post_dominators[header]
+            .filter(|p| exits.contains(p))
+            .or_else(|| exits.iter().next().cloned());
+
+         (header, Loop {
+            body,
+            follow,
+         })
+      })
+      .collect()
+}
+
+fn simplify(stmts: Vec<Stmt>, gotos: &BTreeSet<usize>) -> Vec<Stmt> {
+   let mut result = vec![];
+
+   for stmt in stmts {
+      match stmt {
+         Stmt::Label(ip) => {
+            if gotos.contains(&ip) {
+               result.push(Stmt::Label(ip));
+            }
+         }
+         Stmt::If(condition, then, otherwise) => {
+            let then = simplify(then, gotos);
+            let otherwise = simplify(otherwise, gotos);
+
+            if then.is_empty() && otherwise.is_empty() {
+               continue;
+            } else if then.is_empty() {
+               result.push(Stmt::If(condition.negate(), otherwise, vec![]));
+            } else if !otherwise.is_empty() && ends_diverging(&otherwise) && !ends_diverging(&then) {
+               // `if c { a } else { b; break }` reads better as an early exit
+               result.push(Stmt::If(condition.negate(), otherwise, vec![]));
+               result.extend(then);
+            } else if !otherwise.is_empty() && ends_diverging(&then) {
+               result.push(Stmt::If(condition, then, vec![]));
+               result.extend(otherwise);
+            } else {
+               result.push(Stmt::If(condition, then, otherwise));
+            }
+         }
+         Stmt::Loop(label, body) => {
+            let mut body = simplify(body, gotos);
+            strip_trailing_continue(&mut body, label);
+            let body = simplify(body, gotos);
+
+            // a loop that starts by checking whether to leave is a while loop
+            match body.first() {
+               Some(Stmt::If(condition, then, otherwise)) if then == &[Stmt::Break(label)] && otherwise.is_empty() => {
+                  let condition = condition.clone().negate();
+                  result.push(Stmt::While(label, condition, body[1..].to_vec()));
+               }
+               _ => result.push(Stmt::Loop(label, body)),
+            }
+         }
+         other => result.push(other),
+      }
+   }
+
+   result
+}
+
+/// Falling off the end of a loop body already continues it
+fn strip_trailing_continue(stmts: &mut Vec<Stmt>, label: usize) {
+   match stmts.last_mut() {
+      Some(Stmt::Continue(l)) if *l == label => {
+         stmts.pop();
+      }
+      Some(Stmt::If(_, then, otherwise)) => {
+         strip_trailing_continue(then, label);
+         strip_trailing_continue(otherwise, label);
+      }
+      _ => {}
+   }
+}
+
+struct Printer<'a, 'b> {
+   f: &'a mut fmt::Formatter<'b>,
+   /// loops referenced with break or continue from inside a nested loop need a label
+   labelled: BTreeSet<usize>,
+}
+
+impl<'a, 'b> Printer<'a, 'b> {
+   fn line(&mut self, depth: usize, line: fmt::Arguments) -> fmt::Result {
+      writeln!(self.f, "{:width$}{}", "", line, width = depth * 3)
+   }
+
+   fn loop_label(&self, label: usize) -> String {
+      if self.labelled.contains(&label) {
+         format!("'l{}: ", label)
+      } else {
+         String::new()
+      }
+   }
+
+   fn print(&mut self, stmts: &[Stmt], depth: usize, loops: &mut Vec<usize>) -> fmt::Result {
+      for stmt in stmts {
+         match stmt {
+            Stmt::Assign(register, value) => {
+               match compound(*register, value) {
+                  Some((op, rest)) => self.line(depth, format_args!("r{} {}= {};", register, op, rest))?,
+                  None => self.line(depth, format_args!("r{} = {};", register, value))?,
+               }
+            }
+            Stmt::If(condition, then, otherwise) => {
+               self.line(depth, format_args!("if {} {{", condition))?;
+               self.print(then, depth + 1, loops)?;
+               self.print_else(otherwise, depth, loops)?;
+            }
+            Stmt::Loop(label, body) => {
+               self.line(depth, format_args!("{}loop {{", self.loop_label(*label)))?;
+               loops.push(*label);
+               self.print(body, depth + 1, loops)?;
+               loops.pop();
+               self.line(depth, format_args!("}}"))?;
+            }
+            Stmt::While(label, condition, body) => {
+               self.line(depth, format_args!("{}while {} {{", self.loop_label(*label), condition))?;
+               loops.push(*label);
+               self.print(body, depth + 1, loops)?;
+               loops.pop();
+               self.line(depth, format_args!("}}"))?;
+            }
+            Stmt::Break(label) => {
+               if loops.last() == Some(label) {
+                  self.line(depth, format_args!("break;"))?;
+               } else {
+                  self.line(depth, format_args!("break 'l{};", label))?;
+               }
+            }
+            Stmt::Continue(label) => {
+               if loops.last() == Some(label) {
+                  self.line(depth, format_args!("continue;"))?;
+               } else {
+                  self.line(depth, format_args!("continue 'l{};", label))?;
+               }
+            }
+            Stmt::Return => self.line(depth, format_args!("return;"))?,
+            Stmt::Goto(ip) => self.line(depth, format_args!("goto L{};", ip))?,
+            Stmt::Label(ip) => self.line(depth.saturating_sub(1), format_args!("L{}:", ip))?,
+            Stmt::ComputedGoto(target) => self.line(depth, format_args!("goto *({});", target))?,
+            Stmt::Comment(comment) => self.line(depth, format_args!("// {}", comment))?,
+         }
+      }
+
+      Ok(())
+   }
+
+   fn print_else(&mut self, otherwise: &[Stmt], depth: usize, loops: &mut Vec<usize>) -> fmt::Result {
+      match otherwise {
+         [] => self.line(depth, format_args!("}}")),
+         [Stmt::If(condition, then, otherwise)] => {
+            self.line(depth, format_args!("}} else if {} {{", condition))?;
+            self.print(then, depth + 1, loops)?;
+            self.print_else(otherwise, depth, loops)
+         }
+         _ => {
+            self.line(depth, format_args!("}} else {{"))?;
+            self.print(otherwise, depth + 1, loops)?;
+            self.line(depth, format_args!("}}"))
+         }
+      }
+   }
+}
+
+/// Splits `r = r op x` into `op` and `x` so we can print `r op= x`
+fn compound(register: usize, value: &Expr) -> Option<(&'static str, &Expr)> {
+   if let Expr::Bin(op, left, right) = value {
+      let symbol = match op {
+         BinOp::Add => "+",
+         BinOp::Mul => "*",
+         BinOp::BitAnd => "&",
+         BinOp::BitOr => "|",
+         _ => return None,
+      };
+
+      if **left == Expr::Reg(register) {
+         return Some((symbol, right));
+      } else if **right == Expr::Reg(register) {
+         // all of our compound operators are commutative
+         return Some((symbol, left));
+      }
+   }
+
+   None
+}
+
+/// Finds loops that are broken out of or continued from inside another loop
+fn find_labelled(stmts: &[Stmt], loops: &mut Vec<usize>, labelled: &mut BTreeSet<usize>) {
+   for stmt in stmts {
+      match stmt {
+         Stmt::If(_, then, otherwise) => {
+            find_labelled(then, loops, labelled);
+            find_labelled(otherwise, loops, labelled);
+         }
+         Stmt::Loop(label, body) | Stmt::While(label, _, body) => {
+            loops.push(*label);
+            find_labelled(body, loops, labelled);
+            loops.pop();
+         }
+         Stmt::Break(label) | Stmt::Continue(label) if loops.last() != Some(label) => {
+            labelled.insert(*label);
+         }
+         _ => {}
+      }
+   }
+}
+
+impl fmt::Display for Decompiled {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self.ip_register {
+         Some(ip_register) => writeln!(f, "// decompiled from {} instructions with #ip {}", self.instructions, ip_register)?,
+         None => writeln!(f, "// decompiled from {} instructions", self.instructions)?,
+      }
+      for (start, end) in self.unreachable.iter() {
+         writeln!(f, "// instructions {}..{} are unreachable", start, end)?;
+      }
+
+      let parameters: Vec<String> = self.registers.iter()
+         .map(|r| format!("mut r{}: usize", r))
+         .collect();
+      writeln!(f, "fn program({}) {{", parameters.join(", "))?;
+
+      let mut labelled = BTreeSet::new();
+      find_labelled(&self.body, &mut vec![], &mut labelled);
+
+      let mut printer = Printer {
+         f,
+         labelled,
+      };
+      printer.print(&self.body, 1, &mut vec![])?;
+
+      writeln!(printer.f, "}}")
+   }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+
+use crate::op_code::OpCode;
+use crate::program::Instruction;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum BinOp {
+   Add,
+   Mul,
+   BitAnd,
+   BitOr,
+   Gt,
+   Le,
+   Eq,
+   Ne,
+}
+
+impl BinOp {
+   fn precedence(&self) -> u8 {
+      match self {
+         BinOp::Mul => 5,
+         BinOp::Add => 4,
+         BinOp::BitAnd => 3,
+         BinOp::BitOr => 2,
+         BinOp::Gt | BinOp::Le | BinOp::Eq | BinOp::Ne => 1,
+      }
+   }
+
+   fn symbol(&self) -> &'static str {
+      match self {
+         BinOp::Add => "+",
+         BinOp::Mul => "*",
+         BinOp::BitAnd => "&",
+         BinOp::BitOr => "|",
+         BinOp::Gt => ">",
+         BinOp::Le => "<=",
+         BinOp::Eq => "==",
+         BinOp::Ne => "!=",
+      }
+   }
+
+   pub fn is_comparison(&self) -> bool {
+      self.precedence() == 1
+   }
+
+   // nothing when our result doesn't fit
+   fn apply(&self, left: usize, right: usize) -> Option<usize> {
+      match self {
+         BinOp::Add => left.checked_add(right),
+         BinOp::Mul => left.checked_mul(right),
+         BinOp::BitAnd => Some(left & right),
+         BinOp::BitOr => Some(left | right),
+         BinOp::Gt => Some(if left > right { 1 } else { 0 }),
+         BinOp::Le => Some(if left <= right { 1 } else { 0 }),
+         BinOp::Eq => Some(if left == right { 1 } else { 0 }),
+         BinOp::Ne => Some(if left != right { 1 } else { 0 }),
+      }
+   }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Expr {
+   Reg(usize),
+   Const(usize),
+   Bin(BinOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+   pub fn bin(op: BinOp, left: Expr, right: Expr) -> Expr {
+      Expr::Bin(op, Box::new(left), Box::new(right)).fold()
+   }
+
+   /// Builds the expression an instruction writes into register `c`, reading `ip_register` as `ip`
+   pub fn for_instruction(instruction: &Instruction, ip_register: Option<usize>, ip: usize) -> Expr {
+      let register = |r: usize| {
+         if Some(r) == ip_register {
+            Expr::Const(ip)
+         } else {
+            Expr::Reg(r)
+         }
+      };
+      let a = if instruction.op_code.reads_a() { register(instruction.a) } else { Expr::Const(instruction.a) };
+      let b = if instruction.op_code.reads_b() { register(instruction.b) } else { Expr::Const(instruction.b) };
+
+      match instruction.op_code {
+         OpCode::Addr | OpCode::Addi => Expr::bin(BinOp::Add, a, b),
+         OpCode::Mulr | OpCode::Muli => Expr::bin(BinOp::Mul, a, b),
+         OpCode::Banr | OpCode::Bani => Expr::bin(BinOp::BitAnd, a, b),
+         OpCode::Borr | OpCode::Bori => Expr::bin(BinOp::BitOr, a, b),
+         OpCode::Setr | OpCode::Seti => a,
+         OpCode::Gtir | OpCode::Gtri | OpCode::Gtrr => Expr::bin(BinOp::Gt, a, b),
+         OpCode::Eqir | OpCode::Eqri | OpCode::Eqrr => Expr::bin(BinOp::Eq, a, b),
+      }
+   }
+
+   pub fn is_comparison(&self) -> bool {
+      match self {
+         Expr::Bin(op, _, _) => op.is_comparison(),
+         _ => false,
+      }
+   }
+
+   pub fn as_const(&self) -> Option<usize> {
+      match self {
+         Expr::Const(value) => Some(*value),
+         _ => None,
+      }
+   }
+
+   /// Treat this expression as a condition, anything that isn't a comparison is compared against 0
+   pub fn truthy(self) -> Expr {
+      if self.is_comparison() {
+         self
+      } else {
+         Expr::bin(BinOp::Ne, self, Expr::Const(0))
+      }
+   }
+
+   /// Negates a condition built with `truthy`
+   pub fn negate(self) -> Expr {
+      match self {
+         Expr::Bin(op, left, right) if op.is_comparison() => {
+            let negated = match op {
+               BinOp::Gt => BinOp::Le,
+               BinOp::Le => BinOp::Gt,
+               BinOp::Eq => BinOp::Ne,
+               _ => BinOp::Eq,
+            };
+
+            Expr::Bin(negated, left, right)
+         }
+         other => Expr::bin(BinOp::Eq, other, Expr::Const(0)),
+      }
+   }
+
+   pub fn reads(&self, register: usize) -> bool {
+      self.count_reads(register) > 0
+   }
+
+   pub fn count_reads(&self, register: usize) -> usize {
+      match self {
+         Expr::Reg(r) => if *r == register { 1 } else { 0 },
+         Expr::Const(_) => 0,
+         Expr::Bin(_, left, right) => left.count_reads(register) + right.count_reads(register),
+      }
+   }
+
+   pub fn registers(&self, into: &mut Vec<usize>) {
+      match self {
+         Expr::Reg(r) => into.push(*r),
+         Expr::Const(_) => {}
+         Expr::Bin(_, left, right) => {
+            left.registers(into);
+            right.registers(into);
+         }
+      }
+   }
+
+   /// Replaces every read of `register` with `with`
+   pub fn substitute(&self, register: usize, with: &Expr) -> Expr {
+      match self {
+         Expr::Reg(r) if *r == register => with.clone(),
+         Expr::Bin(op, left, right) => Expr::bin(*op, left.substitute(register, with), right.substitute(register, with)),
+         other => other.clone(),
+      }
+   }
+
+   fn fold(self) -> Expr {
+      match self {
+         Expr::Bin(op, left, right) => match (left.as_const(), right.as_const()) {
+            (Some(l), Some(r)) => match op.apply(l, r) {
+               Some(value) => Expr::Const(value),
+               // leave anything that overflows for whoever runs it to deal with
+               None => Expr::Bin(op, left, right),
+            },
+            // comparisons are already 0 or 1 so testing them against 0 is redundant
+            (None, Some(0)) if op == BinOp::Ne && left.is_comparison() => *left,
+            (None, Some(0)) if op == BinOp::Eq && left.is_comparison() => left.negate(),
+            _ => Expr::Bin(op, left, right),
+         }
+         other => other,
+      }
+   }
+
+   fn precedence(&self) -> u8 {
+      match self {
+         Expr::Bin(op, _, _) => op.precedence(),
+         _ => u8::MAX,
+      }
+   }
+
+   fn fmt_child(&self, f: &mut fmt::Formatter, parent: u8) -> fmt::Result {
+      // comparisons don't chain in Rust so always wrap them
+      if self.precedence() < parent || (self.precedence() == 1 && parent == 1) {
+         write!(f, "({})", self)
+      } else {
+         write!(f, "{}", self)
+      }
+   }
+}
+
+impl fmt::Display for Expr {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Expr::Reg(r) => write!(f, "r{}", r),
+         Expr::Const(value) => write!(f, "{}", value),
+         Expr::Bin(op, left, right) => {
+            left.fmt_child(f, op.precedence())?;
+            write!(f, " {} ", op.symbol())?;
+            // we're left associative so an equal precedence on the right needs parens
+            right.fmt_child(f, op.precedence() + 1)
+         }
+      }
+   }
+}
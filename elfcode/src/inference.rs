@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::op_code::{OpCode, OP_CODES};
+use crate::program::ManualStep;
+
+pub type OpMapping = HashMap<usize, OpCode>;
+
+/// How many mappings we'll list for an ambiguous manual before giving up
+pub const AMBIGUOUS_LIMIT: usize = 100;
+
+pub enum Solution {
+   Unique(OpMapping),
+   /// every consistent mapping, up to `AMBIGUOUS_LIMIT` of them
+   Ambiguous(Vec<OpMapping>),
+}
+
+/// Why no mapping can satisfy the manual, along with the samples responsible
+#[derive(Debug)]
+pub struct Conflict {
+   pub reason: String,
+   /// indexes into the manual
+   pub samples: Vec<usize>,
+}
+
+impl fmt::Display for Conflict {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      let samples: Vec<String> = self.samples.iter().map(|sample| sample.to_string()).collect();
+
+      write!(f, "{} (samples {})", self.reason, samples.join(", "))
+   }
+}
+
+pub struct Inference<'a> {
+   manual: &'a [ManualStep],
+   /// the op codes that explain each sample on its own
+   pub candidates: Vec<Vec<OpCode>>,
+   /// the op codes that explain every sample for each op id
+   pub possible: BTreeMap<usize, Vec<OpCode>>,
+}
+
+impl<'a> Inference<'a> {
+   pub fn new(manual: &'a [ManualStep]) -> Inference<'a> {
+      let candidates: Vec<Vec<OpCode>> = manual.iter()
+         .map(|step| {
+            OP_CODES.iter()
+               .filter(|op_code| step.matches(**op_code))
+               .cloned()
+               .collect()
+         })
+         .collect();
+
+      let mut possible: BTreeMap<usize, Vec<OpCode>> = BTreeMap::new();
+      for (step, candidates) in manual.iter().zip(candidates.iter()) {
+         possible.entry(step.instruction.op_id)
+            .or_insert_with(|| OP_CODES.to_vec())
+            .retain(|op_code| candidates.contains(op_code));
+      }
+
+      Inference {
+         manual,
+         candidates,
+         possible,
+      }
+   }
+
+   fn samples_for(&self, op_id: usize) -> Vec<usize> {
+      self.manual.iter().enumerate()
+         .filter(|(_, step)| step.instruction.op_id == op_id)
+         .map(|(i, _)| i)
+         .collect()
+   }
+
+   /// Explains the first reason no mapping exists, if there is one
+   pub fn conflict(&self) -> Option<Conflict> {
+      if let Some(sample) = self.candidates.iter().position(|candidates| candidates.is_empty()) {
+         return Some(Conflict {
+            reason: format!("no op code explains op {}", self.manual[sample].instruction.op_id),
+            samples: vec![sample],
+         });
+      }
+
+      for (op_id, possible) in self.possible.iter() {
+         if possible.is_empty() {
+            // keep only the samples that narrowed things down on the way to nothing
+            let mut remaining = OP_CODES.to_vec();
+            let mut samples = vec![];
+            for sample in self.samples_for(*op_id) {
+               let before = remaining.len();
+               remaining.retain(|op_code| self.candidates[sample].contains(op_code));
+               if remaining.len() < before {
+                  samples.push(sample);
+               }
+               if remaining.is_empty() {
+                  break;
+               }
+            }
+
+            return Some(Conflict {
+               reason: format!("no single op code explains every sample of op {}", op_id),
+               samples,
+            });
+         }
+      }
+
+      self.hall_violation()
+   }
+
+   /// Finds a group of op ids that have fewer op codes between them than there are op ids
+   fn hall_violation(&self) -> Option<Conflict> {
+      let mut matched: HashMap<OpCode, usize> = HashMap::new();
+
+      for op_id in self.possible.keys() {
+         let mut visited = vec![];
+         if !self.augment(*op_id, &mut matched, &mut visited) {
+            // everything reachable along alternating paths is fighting over the same op codes
+            let mut op_ids = vec![*op_id];
+            let mut op_codes: Vec<OpCode> = vec![];
+            let mut i = 0;
+            while i < op_ids.len() {
+               for op_code in self.possible[&op_ids[i]].iter() {
+                  if !op_codes.contains(op_code) {
+                     op_codes.push(*op_code);
+                     if let Some(other) = matched.get(op_code) {
+                        if !op_ids.contains(other) {
+                           op_ids.push(*other);
+                        }
+                     }
+                  }
+               }
+               i += 1;
+            }
+            op_ids.sort();
+
+            let names: Vec<String> = op_codes.iter().map(|op_code| op_code.to_string()).collect();
+            let ids: Vec<String> = op_ids.iter().map(|op_id| op_id.to_string()).collect();
+
+            return Some(Conflict {
+               reason: format!("ops {} can only be {}", ids.join(", "), names.join(", ")),
+               samples: op_ids.iter().flat_map(|op_id| self.samples_for(*op_id)).collect(),
+            });
+         }
+      }
+
+      None
+   }
+
+   fn augment(&self, op_id: usize, matched: &mut HashMap<OpCode, usize>, visited: &mut Vec<OpCode>) -> bool {
+      for op_code in self.possible[&op_id].iter() {
+         if visited.contains(op_code) {
+            continue;
+         }
+         visited.push(*op_code);
+
+         let free = match matched.get(op_code) {
+            Some(other) => self.augment(*other, matched, visited),
+            None => true,
+         };
+         if free {
+            matched.insert(*op_code, op_id);
+            return true;
+         }
+      }
+
+      false
+   }
+
+   /// Every mapping that explains the whole manual, stopping once we have `limit` of them
+   pub fn mappings(&self, limit: usize) -> Vec<OpMapping> {
+      // try the most constrained op ids first
+      let mut op_ids: Vec<usize> = self.possible.keys().cloned().collect();
+      op_ids.sort_by_key(|op_id| self.possible[op_id].len());
+
+      let mut mappings = vec![];
+      self.search(&op_ids, &mut HashMap::new(), &mut mappings, limit);
+
+      mappings
+   }
+
+   fn search(&self, op_ids: &[usize], mapping: &mut OpMapping, mappings: &mut Vec<OpMapping>, limit: usize) {
+      if mappings.len() >= limit {
+         return;
+      }
+
+      let (op_id, rest) = match op_ids.split_first() {
+         Some(split) => split,
+         None => {
+            mappings.push(mapping.clone());
+            return;
+         }
+      };
+
+      for op_code in self.possible[op_id].iter() {
+         if !mapping.values().any(|used| used == op_code) {
+            mapping.insert(*op_id, *op_code);
+            self.search(rest, mapping, mappings, limit);
+            mapping.remove(op_id);
+         }
+      }
+   }
+
+   pub fn solve(&self) -> Result<Solution, Conflict> {
+      if let Some(conflict) = self.conflict() {
+         return Err(conflict);
+      }
+
+      let mut mappings = self.mappings(AMBIGUOUS_LIMIT);
+      if mappings.len() == 1 {
+         Ok(Solution::Unique(mappings.remove(0)))
+      } else {
+         Ok(Solution::Ambiguous(mappings))
+      }
+   }
+}
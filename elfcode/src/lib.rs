@@ -0,0 +1,13 @@
+pub mod op_code;
+pub mod program;
+pub mod machine;
+pub mod debugger;
+pub mod expr;
+pub mod cfg;
+pub mod decompiler;
+pub mod optimizer;
+pub mod inference;
+
+pub use crate::op_code::{OpCode, OP_CODES};
+pub use crate::program::{Instruction, NumericInstruction, Program, Manual, ManualStep, REGISTERS};
+pub use crate::machine::Machine;
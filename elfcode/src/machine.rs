@@ -0,0 +1,116 @@
+use crate::optimizer;
+use crate::optimizer::Native;
+use crate::program::Instruction;
+use crate::program::Program;
+
+pub struct Machine<'a> {
+   program: &'a Program,
+   registers: Vec<usize>,
+   ip: usize,
+   executed: u64,
+   /// loops we run natively instead of instruction by instruction, indexed by their header
+   natives: Vec<Option<Native>>,
+}
+
+impl<'a> Machine<'a> {
+   pub fn new(program: &'a Program, registers: Vec<usize>) -> Machine<'a> {
+      let ip = program.ip_register
+         .map(|ip_register| registers[ip_register])
+         .unwrap_or(0);
+
+      Machine {
+         program,
+         registers,
+         ip,
+         executed: 0,
+         natives: vec![],
+      }
+   }
+
+   /// A machine that swaps any hot loops it recognizes for native code
+   pub fn optimized(program: &'a Program, registers: Vec<usize>) -> Machine<'a> {
+      let mut machine = Machine::new(program, registers);
+      machine.natives = optimizer::optimize(program);
+
+      machine
+   }
+
+   pub fn program(&self) -> &'a Program {
+      self.program
+   }
+
+   pub fn registers(&self) -> &[usize] {
+      &self.registers
+   }
+
+   pub fn registers_mut(&mut self) -> &mut [usize] {
+      &mut self.registers
+   }
+
+   pub fn ip(&self) -> usize {
+      self.ip
+   }
+
+   pub fn set_ip(&mut self, ip: usize) {
+      self.ip = ip;
+   }
+
+   /// How many instructions we've run so far
+   pub fn executed(&self) -> u64 {
+      self.executed
+   }
+
+   pub fn halted(&self) -> bool {
+      self.ip >= self.program.len()
+   }
+
+   /// The instruction we'll run on the next step
+   pub fn current(&self) -> Option<&'a Instruction> {
+      self.program.instructions.get(self.ip)
+   }
+
+   /// Runs a single instruction, returning false if we've already halted
+   pub fn step(&mut self) -> bool {
+      let instruction = match self.current() {
+         Some(instruction) => instruction,
+         None => return false,
+      };
+
+      if let Some(Some(native)) = self.natives.get(self.ip) {
+         if let Some(ip_register) = self.program.ip_register {
+            self.registers[ip_register] = self.ip;
+         }
+         self.ip = native.run(&mut self.registers);
+         if let Some(ip_register) = self.program.ip_register {
+            // the jump out of the loop leaves the register one behind where we continue
+            self.registers[ip_register] = self.ip - 1;
+         }
+         self.executed += 1;
+
+         return true;
+      }
+
+      match self.program.ip_register {
+         Some(ip_register) => {
+            self.registers[ip_register] = self.ip;
+            instruction.execute(&mut self.registers);
+            self.ip = self.registers[ip_register] + 1;
+         }
+         None => {
+            instruction.execute(&mut self.registers);
+            self.ip += 1;
+         }
+      }
+
+      self.executed += 1;
+
+      true
+   }
+
+   /// Runs until our instruction pointer leaves the program
+   pub fn run(&mut self) -> &[usize] {
+      while self.step() {}
+
+      &self.registers
+   }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum OpCode {
+   Addr,
+   Addi,
+   Mulr,
+   Muli,
+   Banr,
+   Bani,
+   Borr,
+   Bori,
+   Setr,
+   Seti,
+   Gtir,
+   Gtri,
+   Gtrr,
+   Eqir,
+   Eqri,
+   Eqrr,
+}
+
+pub const OP_CODES: [OpCode; 16] = [
+   OpCode::Addr, OpCode::Addi, OpCode::Mulr, OpCode::Muli,
+   OpCode::Banr, OpCode::Bani, OpCode::Borr, OpCode::Bori,
+   OpCode::Setr, OpCode::Seti, OpCode::Gtir, OpCode::Gtri,
+   OpCode::Gtrr, OpCode::Eqir, OpCode::Eqri, OpCode::Eqrr,
+];
+
+impl OpCode {
+   pub fn from_mnemonic(mnemonic: &str) -> Option<OpCode> {
+      OP_CODES.iter()
+         .find(|op_code| op_code.to_string() == mnemonic)
+         .cloned()
+   }
+
+   /// Runs this operation against the registers in place, writing the result into register `c`
+   #[inline]
+   pub fn execute(&self, a: usize, b: usize, c: usize, registers: &mut [usize]) {
+      registers[c] = self.evaluate(a, b, registers);
+   }
+
+   /// Computes the value this operation would write into register `c` without writing it
+   #[inline]
+   pub fn evaluate(&self, a: usize, b: usize, registers: &[usize]) -> usize {
+      match self {
+         OpCode::Addr => registers[a] + registers[b],
+         OpCode::Addi => registers[a] + b,
+         OpCode::Mulr => registers[a] * registers[b],
+         OpCode::Muli => registers[a] * b,
+         OpCode::Banr => registers[a] & registers[b],
+         OpCode::Bani => registers[a] & b,
+         OpCode::Borr => registers[a] | registers[b],
+         OpCode::Bori => registers[a] | b,
+         OpCode::Setr => registers[a],
+         OpCode::Seti => a,
+         OpCode::Gtir => if a > registers[b] { 1 } else { 0 },
+         OpCode::Gtri => if registers[a] > b { 1 } else { 0 },
+         OpCode::Gtrr => if registers[a] > registers[b] { 1 } else { 0 },
+         OpCode::Eqir => if a == registers[b] { 1 } else { 0 },
+         OpCode::Eqri => if registers[a] == b { 1 } else { 0 },
+         OpCode::Eqrr => if registers[a] == registers[b] { 1 } else { 0 },
+      }
+   }
+
+   /// Is the `a` input read from a register
+   pub fn reads_a(&self) -> bool {
+      !matches!(self, OpCode::Seti | OpCode::Gtir | OpCode::Eqir)
+   }
+
+   /// Is the `b` input read from a register
+   pub fn reads_b(&self) -> bool {
+      matches!(self, OpCode::Addr | OpCode::Mulr | OpCode::Banr | OpCode::Borr |
+         OpCode::Gtir | OpCode::Gtrr | OpCode::Eqir | OpCode::Eqrr)
+   }
+}
+
+impl fmt::Display for OpCode {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{}", format!("{:?}", self).to_lowercase())
+   }
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+
+use crate::op_code::OpCode;
+use crate::program::Program;
+
+/// A hot loop we can replace with a native calculation, entered at the loop's header
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Native {
+   /// `for a in a.. { for b in 1.. { if a * b == target { sum += a } } }` until both pass `target`
+   DivisorSum {
+      outer: usize,
+      inner: usize,
+      target: usize,
+      sum: usize,
+      flag: usize,
+      exit: usize,
+   },
+   /// the inner loop of `DivisorSum` on its own
+   DivisorTest {
+      factor: usize,
+      counter: usize,
+      target: usize,
+      sum: usize,
+      flag: usize,
+      exit: usize,
+   },
+   /// counts `quotient` up until `(quotient + 1) * divisor > dividend`
+   Divide {
+      quotient: usize,
+      divisor: usize,
+      dividend: usize,
+      flag: usize,
+      exit: usize,
+   },
+}
+
+impl Native {
+   /// Leaves the registers how the loop would have and returns the instruction we continue at
+   pub fn run(&self, registers: &mut [usize]) -> usize {
+      match *self {
+         Native::DivisorSum { outer, inner, target, sum, flag, exit } => {
+            let n = registers[target];
+            let a = registers[outer];
+
+            registers[sum] += sum_of_divisors(n, a);
+            registers[outer] = a.max(n) + 1;
+            registers[inner] = n.max(1) + 1;
+            registers[flag] = 1;
+
+            exit
+         }
+         Native::DivisorTest { factor, counter, target, sum, flag, exit } => {
+            let n = registers[target];
+            let a = registers[factor];
+            let b = registers[counter];
+            let last = b.max(n);
+
+            if a != 0 && n.is_multiple_of(a) && b <= n / a && n / a <= last {
+               registers[sum] += a;
+            }
+            registers[counter] = last + 1;
+            registers[flag] = 1;
+
+            exit
+         }
+         Native::Divide { quotient, divisor, dividend, flag, exit } => {
+            registers[quotient] = registers[quotient].max(registers[dividend] / divisor);
+            registers[flag] = 1;
+
+            exit
+         }
+      }
+   }
+}
+
+impl fmt::Display for Native {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Native::DivisorSum { outer, target, sum, exit, .. } =>
+            write!(f, "r{} += divisors of r{} from r{}, goto {}", sum, target, outer, exit),
+         Native::DivisorTest { factor, target, sum, exit, .. } =>
+            write!(f, "r{} += r{} if it divides r{}, goto {}", sum, factor, target, exit),
+         Native::Divide { quotient, divisor, dividend, exit, .. } =>
+            write!(f, "r{} = r{} / {}, goto {}", quotient, dividend, divisor, exit),
+      }
+   }
+}
+
+/// Sum of every divisor of `n` that is at least `from`
+fn sum_of_divisors(n: usize, from: usize) -> usize {
+   let mut sum = 0;
+
+   let mut d = 1;
+   while d * d <= n {
+      if n.is_multiple_of(d) {
+         let pair = n / d;
+         if d >= from {
+            sum += d;
+         }
+         if pair != d && pair >= from {
+            sum += pair;
+         }
+      }
+
+      d += 1;
+   }
+
+   sum
+}
+
+/// Finds the loops we know how to replace, indexed by the instruction they replace
+pub fn optimize(program: &Program) -> Vec<Option<Native>> {
+   (0..program.len())
+      .map(|ip| {
+         let mut matcher = Matcher::new(program, ip);
+
+         match_divisor_sum(&mut matcher)
+            .or_else(|| match_divisor_test(matcher.reset()))
+            .or_else(|| match_divide(matcher.reset()))
+      })
+      .collect()
+}
+
+// pattern variables
+const A: usize = 0;
+const B: usize = 1;
+const N: usize = 2;
+const S: usize = 3;
+const T: usize = 4;
+const Q: usize = 5;
+const D: usize = 6;
+const K: usize = 7;
+const E: usize = 8;
+const VARIABLES: usize = 9;
+
+#[derive(Copy, Clone)]
+enum Arg {
+   /// a register bound to a pattern variable
+   Reg(usize),
+   /// a value bound to a pattern variable
+   Val(usize),
+   /// the instruction pointer's register
+   Ip,
+   Lit(usize),
+   Any,
+}
+
+struct Matcher<'a> {
+   program: &'a Program,
+   header: usize,
+   at: usize,
+   bound: [Option<usize>; VARIABLES],
+   registers: Vec<usize>,
+}
+
+impl<'a> Matcher<'a> {
+   fn new(program: &'a Program, header: usize) -> Matcher<'a> {
+      Matcher {
+         program,
+         header,
+         at: header,
+         bound: [None; VARIABLES],
+         registers: vec![],
+      }
+   }
+
+   fn reset(&mut self) -> &mut Matcher<'a> {
+      self.at = self.header;
+      self.bound = [None; VARIABLES];
+      self.registers.clear();
+
+      self
+   }
+
+   fn get(&self, variable: usize) -> usize {
+      self.bound[variable].unwrap()
+   }
+
+   fn bind(&mut self, arg: Arg, value: usize) -> bool {
+      match arg {
+         Arg::Reg(variable) => {
+            match self.bound[variable] {
+               Some(bound) => bound == value,
+               // every register variable has to be a different register
+               None if Some(value) == self.program.ip_register || self.registers.contains(&value) => false,
+               None => {
+                  self.bound[variable] = Some(value);
+                  self.registers.push(value);
+                  true
+               }
+            }
+         }
+         Arg::Val(variable) => {
+            *self.bound[variable].get_or_insert(value) == value
+         }
+         Arg::Ip => Some(value) == self.program.ip_register,
+         Arg::Lit(literal) => literal == value,
+         Arg::Any => true,
+      }
+   }
+
+   fn try_bind(&mut self, args: [(Arg, usize); 3]) -> bool {
+      let bound = self.bound;
+      let registers = self.registers.len();
+
+      if args.iter().all(|(arg, value)| self.bind(*arg, *value)) {
+         true
+      } else {
+         self.bound = bound;
+         self.registers.truncate(registers);
+         false
+      }
+   }
+
+   /// Matches the next instruction, commutative op codes can have `a` and `b` either way around
+   fn next(&mut self, op_code: OpCode, a: Arg, b: Arg, c: Arg) -> bool {
+      let instruction = match self.program.instructions.get(self.at) {
+         Some(instruction) if instruction.op_code == op_code => *instruction,
+         _ => return false,
+      };
+      self.at += 1;
+
+      let commutative = matches!(op_code, OpCode::Addr | OpCode::Mulr | OpCode::Banr | OpCode::Borr | OpCode::Eqrr);
+
+      self.try_bind([(a, instruction.a), (b, instruction.b), (c, instruction.c)]) ||
+         (commutative && self.try_bind([(a, instruction.b), (b, instruction.a), (c, instruction.c)]))
+   }
+
+   /// A `seti` into the ip register that jumps back to `header`
+   fn jump_to(&mut self, header: usize) -> bool {
+      header > 0 && self.next(OpCode::Seti, Arg::Lit(header - 1), Arg::Any, Arg::Ip)
+   }
+}
+
+fn match_divisor_test(m: &mut Matcher) -> Option<Native> {
+   let header = m.at;
+
+   let matched = m.next(OpCode::Mulr, Arg::Reg(A), Arg::Reg(B), Arg::Reg(T)) &&
+      m.next(OpCode::Eqrr, Arg::Reg(T), Arg::Reg(N), Arg::Reg(T)) &&
+      m.next(OpCode::Addr, Arg::Reg(T), Arg::Ip, Arg::Ip) &&
+      m.next(OpCode::Addi, Arg::Ip, Arg::Lit(1), Arg::Ip) &&
+      m.next(OpCode::Addr, Arg::Reg(A), Arg::Reg(S), Arg::Reg(S)) &&
+      m.next(OpCode::Addi, Arg::Reg(B), Arg::Lit(1), Arg::Reg(B)) &&
+      m.next(OpCode::Gtrr, Arg::Reg(B), Arg::Reg(N), Arg::Reg(T)) &&
+      m.next(OpCode::Addr, Arg::Ip, Arg::Reg(T), Arg::Ip) &&
+      m.jump_to(header);
+
+   if matched {
+      Some(Native::DivisorTest {
+         factor: m.get(A),
+         counter: m.get(B),
+         target: m.get(N),
+         sum: m.get(S),
+         flag: m.get(T),
+         exit: m.at,
+      })
+   } else {
+      None
+   }
+}
+
+fn match_divisor_sum(m: &mut Matcher) -> Option<Native> {
+   let header = m.at;
+
+   let matched = m.next(OpCode::Seti, Arg::Lit(1), Arg::Any, Arg::Reg(B)) &&
+      match_divisor_test(m).is_some() &&
+      m.next(OpCode::Addi, Arg::Reg(A), Arg::Lit(1), Arg::Reg(A)) &&
+      m.next(OpCode::Gtrr, Arg::Reg(A), Arg::Reg(N), Arg::Reg(T)) &&
+      m.next(OpCode::Addr, Arg::Reg(T), Arg::Ip, Arg::Ip) &&
+      m.jump_to(header);
+
+   if matched {
+      Some(Native::DivisorSum {
+         outer: m.get(A),
+         inner: m.get(B),
+         target: m.get(N),
+         sum: m.get(S),
+         flag: m.get(T),
+         exit: m.at,
+      })
+   } else {
+      None
+   }
+}
+
+fn match_divide(m: &mut Matcher) -> Option<Native> {
+   let header = m.at;
+
+   let matched = m.next(OpCode::Addi, Arg::Reg(Q), Arg::Lit(1), Arg::Reg(T)) &&
+      m.next(OpCode::Muli, Arg::Reg(T), Arg::Val(K), Arg::Reg(T)) &&
+      m.next(OpCode::Gtrr, Arg::Reg(T), Arg::Reg(D), Arg::Reg(T)) &&
+      m.next(OpCode::Addr, Arg::Reg(T), Arg::Ip, Arg::Ip) &&
+      m.next(OpCode::Addi, Arg::Ip, Arg::Lit(1), Arg::Ip) &&
+      m.next(OpCode::Seti, Arg::Val(E), Arg::Any, Arg::Ip) &&
+      m.next(OpCode::Addi, Arg::Reg(Q), Arg::Lit(1), Arg::Reg(Q)) &&
+      m.jump_to(header) &&
+      m.get(K) > 0;
+
+   if matched {
+      Some(Native::Divide {
+         quotient: m.get(Q),
+         divisor: m.get(K),
+         dividend: m.get(D),
+         flag: m.get(T),
+         exit: m.get(E) + 1,
+      })
+   } else {
+      None
+   }
+}
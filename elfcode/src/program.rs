@@ -0,0 +1,215 @@
+use regex::Regex;
+use std::fmt;
+
+use common::{parse_at, ParseError};
+
+use crate::op_code::OpCode;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Instruction {
+   pub op_code: OpCode,
+   pub a: usize,
+   pub b: usize,
+   pub c: usize,
+}
+
+impl Instruction {
+   pub fn new(op_code: OpCode, a: usize, b: usize, c: usize) -> Instruction {
+      Instruction {
+         op_code,
+         a,
+         b,
+         c,
+      }
+   }
+
+   #[inline]
+   pub fn execute(&self, registers: &mut [usize]) {
+      self.op_code.execute(self.a, self.b, self.c, registers);
+   }
+}
+
+impl fmt::Display for Instruction {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "{} {} {} {}", self.op_code, self.a, self.b, self.c)
+   }
+}
+
+/// An instruction from the day 16 format where the op code is only known by its number
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NumericInstruction {
+   pub op_id: usize,
+   pub a: usize,
+   pub b: usize,
+   pub c: usize,
+}
+
+impl NumericInstruction {
+   pub fn resolve(&self, op_code: OpCode) -> Instruction {
+      Instruction::new(op_code, self.a, self.b, self.c)
+   }
+}
+
+/// How many registers the device running the `#ip` format has
+pub const REGISTERS: usize = 6;
+
+#[derive(Clone, Debug)]
+pub struct Program {
+   /// the register our instruction pointer is bound to, if any
+   pub ip_register: Option<usize>,
+   pub instructions: Vec<Instruction>,
+}
+
+impl Program {
+   pub fn new(ip_register: Option<usize>, instructions: Vec<Instruction>) -> Program {
+      Program {
+         ip_register,
+         instructions,
+      }
+   }
+
+   /// Parses the mnemonic format with an optional leading `#ip <register>` line,
+   /// every register has to be one of our `REGISTERS`
+   pub fn parse(contents: &str) -> Result<Program, ParseError> {
+      let ip_re: Regex = Regex::new(r"^#ip (\d+)$").unwrap();
+      let instruction_re: Regex = Regex::new(r"^([a-z]+) (\d+) (\d+) (\d+)$").unwrap();
+
+      let register = |part: &str| -> Result<usize, ParseError> {
+         let register = parse_at(contents, part)?;
+         if register < REGISTERS {
+            Ok(register)
+         } else {
+            Err(ParseError::at(contents, part, format!("there's no register {}, only 0 to {}", register, REGISTERS - 1)))
+         }
+      };
+
+      let mut ip_register = None;
+      let mut instructions = vec![];
+
+      for row in contents.lines().map(|row| row.trim()).filter(|row| !row.is_empty()) {
+         if let Some(captures) = ip_re.captures(row) {
+            ip_register = Some(register(&captures[1])?);
+         } else {
+            let captures = instruction_re.captures(row)
+               .ok_or_else(|| ParseError::at(contents, row, "expected an instruction like addi 0 1 2 or #ip 0"))?;
+
+            let op_code = OpCode::from_mnemonic(&captures[1])
+               .ok_or_else(|| ParseError::at(contents, &captures[1], format!("unknown op code {:?}", &captures[1])))?;
+
+            let (a, b, c) = (&captures[2], &captures[3], &captures[4]);
+            instructions.push(Instruction::new(
+               op_code,
+               if op_code.reads_a() { register(a)? } else { parse_at(contents, a)? },
+               if op_code.reads_b() { register(b)? } else { parse_at(contents, b)? },
+               register(c)?,
+            ));
+         }
+      }
+
+      Ok(Program::new(ip_register, instructions))
+   }
+
+   pub fn len(&self) -> usize {
+      self.instructions.len()
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.instructions.is_empty()
+   }
+}
+
+impl fmt::Display for Program {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      if let Some(ip_register) = self.ip_register {
+         writeln!(f, "#ip {}", ip_register)?;
+      }
+      for instruction in self.instructions.iter() {
+         writeln!(f, "{}", instruction)?;
+      }
+
+      Ok(())
+   }
+}
+
+pub struct ManualStep {
+   pub before: Vec<usize>,
+   pub instruction: NumericInstruction,
+   pub after: Vec<usize>,
+}
+
+impl ManualStep {
+   /// Would running this step with the given op code produce our after registers
+   pub fn matches(&self, op_code: OpCode) -> bool {
+      let mut registers = self.before.clone();
+      self.instruction.resolve(op_code).execute(&mut registers);
+
+      registers == self.after
+   }
+}
+
+/// The day 16 format: a list of before/after samples followed by a numeric program
+pub struct Manual {
+   pub steps: Vec<ManualStep>,
+   pub program: Vec<NumericInstruction>,
+}
+
+impl Manual {
+   pub fn parse(contents: &str) -> Result<Manual, ParseError> {
+      let before_after_re: Regex = Regex::new(r"Before:\s+\[(\d+), (\d+), (\d+), (\d+)\]\s+(\d+) (\d+) (\d+) (\d+)\s+After:\s+\[(\d+), (\d+), (\d+), (\d+)\]").unwrap();
+
+      let mut last_match_i = 0;
+      let steps: Vec<ManualStep> = before_after_re.captures_iter(contents)
+         .map(|captures| {
+            let mut inner_iter = captures.iter();
+            last_match_i = inner_iter.next().unwrap().unwrap().end();
+
+            let results: Vec<usize> = inner_iter
+               .map(|capture| parse_at(contents, capture.unwrap().as_str()))
+               .collect::<Result<_, _>>()?;
+
+            Ok(ManualStep {
+               before: results[0..4].to_vec(),
+               instruction: NumericInstruction {
+                  op_id: results[4],
+                  a: results[5],
+                  b: results[6],
+                  c: results[7],
+               },
+               after: results[8..12].to_vec(),
+            })
+         })
+         .collect::<Result<_, _>>()?;
+
+      Ok(Manual {
+         steps,
+         program: parse_numeric_rows(contents, &contents[last_match_i..])?,
+      })
+   }
+}
+
+/// Parses rows of `<op id> <a> <b> <c>`
+pub fn parse_numeric(contents: &str) -> Result<Vec<NumericInstruction>, ParseError> {
+   parse_numeric_rows(contents, contents)
+}
+
+// our rows are somewhere in our contents, which is what our errors point into
+fn parse_numeric_rows(contents: &str, rows: &str) -> Result<Vec<NumericInstruction>, ParseError> {
+   let program_re: Regex = Regex::new(r"(\d+) (\d+) (\d+) (\d+)").unwrap();
+
+   rows.trim().lines()
+      .map(|row| {
+         let parsed_row: Vec<usize> = program_re.captures(row)
+            .ok_or_else(|| ParseError::at(contents, row, "expected an instruction like 9 0 1 2"))?
+            .iter().skip(1)
+            .map(|capture| parse_at(contents, capture.unwrap().as_str()))
+            .collect::<Result<_, _>>()?;
+
+         Ok(NumericInstruction {
+            op_id: parsed_row[0],
+            a: parsed_row[1],
+            b: parsed_row[2],
+            c: parsed_row[3],
+         })
+      })
+      .collect()
+}
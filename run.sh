@@ -2,8 +2,4 @@
 
 cargo build --release
 
-for ((i=1;i<=25;i++)); do
-  if [ -d "$i" ]; then
-    cargo run --release --bin $i
-  fi
-done
\ No newline at end of file
+./target/release/aoc run --all "$@"